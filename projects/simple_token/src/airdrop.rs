@@ -0,0 +1,276 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    hash::hashv,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use crate::{errors::SimpleTokenErrors, events::Event, storage};
+
+// each claim bitmap PDA tracks this many recipients, bitmaps are created by the first claimant
+pub const CLAIM_BITMAP_LEN: usize = 1024;
+pub const CLAIMS_PER_BITMAP: u64 = CLAIM_BITMAP_LEN as u64 * 8;
+
+// leaves and inner nodes are hashed with different prefixes so a node can't pass as a leaf
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf(index: u64, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        &index.to_le_bytes(),
+        claimant.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+// pairs are sorted before hashing, so proofs don't need to say which side a sibling is on
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+pub fn verify_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling))
+        == *root
+}
+
+// the whole airdrop is minted into the distributor up front, claims and the clawback move it out
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct Distributor {
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub expiry_ts: i64,
+}
+
+impl Distributor {
+    pub const LEN: usize = 32 + 8 + 8 + 8;
+
+    pub fn remaining(&self) -> u64 {
+        self.total_amount - self.claimed_amount
+    }
+
+    fn get(
+        program_id: &Pubkey,
+        mint: &Pubkey,
+        merkle_root: &[u8; 32],
+        account: &AccountInfo,
+    ) -> Result<Distributor, ProgramError> {
+        verify_distributor_pda(program_id, mint, merkle_root, account)?;
+        storage::load_state(account, "No airdrop for this merkle root")
+    }
+}
+
+pub fn verify_distributor_pda(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    merkle_root: &[u8; 32],
+    distributor_pda: &AccountInfo,
+) -> ProgramResult {
+    storage::verify_pda(
+        program_id,
+        &[b"distributor", mint.as_ref(), merkle_root],
+        distributor_pda,
+    )
+}
+
+pub fn verify_claim_bitmap_pda(
+    program_id: &Pubkey,
+    distributor: &Pubkey,
+    bitmap_index: u64,
+    claim_bitmap_pda: &AccountInfo,
+) -> ProgramResult {
+    storage::verify_pda(
+        program_id,
+        &[
+            b"airdrop_claims",
+            distributor.as_ref(),
+            &bitmap_index.to_le_bytes(),
+        ],
+        claim_bitmap_pda,
+    )
+}
+
+pub fn create_airdrop(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    expiry_ts: i64,
+) -> ProgramResult {
+    msg!("Creating airdrop of {} tokens", total_amount);
+    let accounts_iter = &mut accounts.iter();
+    let owner = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let distributor_pda = next_account_info(accounts_iter)?;
+
+    storage::check_owner(owner, &mint, config_pda, program_id)?;
+    storage::check_not_paused(config_pda)?;
+    verify_distributor_pda(program_id, &mint, &merkle_root, distributor_pda)?;
+
+    if expiry_ts <= Clock::get()?.unix_timestamp {
+        msg!("Airdrop expiry has to be in the future");
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::AirdropExpired as u32,
+        ));
+    }
+
+    storage::create_pda(
+        program_id,
+        owner,
+        &[b"distributor", mint.as_ref(), &merkle_root],
+        distributor_pda,
+        Distributor::LEN,
+    )?;
+    let distributor = Distributor {
+        merkle_root,
+        total_amount,
+        claimed_amount: 0,
+        expiry_ts,
+    };
+    storage::save_state(&distributor, distributor_pda)?;
+
+    storage::increase_supply(total_amount, config_pda)
+}
+
+pub fn claim(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    merkle_root: [u8; 32],
+    index: u64,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> ProgramResult {
+    msg!("Claiming {} airdropped tokens", amount);
+    let accounts_iter = &mut accounts.iter();
+    let claimant = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let distributor_pda = next_account_info(accounts_iter)?;
+    let claim_bitmap_pda = next_account_info(accounts_iter)?;
+    let claimant_pda = next_account_info(accounts_iter)?;
+
+    if !claimant.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidSigner as u32,
+        ));
+    }
+
+    storage::check_config_pda(program_id, &mint, config_pda)?;
+    storage::check_not_paused(config_pda)?;
+    let mut distributor = Distributor::get(program_id, &mint, &merkle_root, distributor_pda)?;
+    if Clock::get()?.unix_timestamp > distributor.expiry_ts {
+        msg!("Airdrop expired at {}", distributor.expiry_ts);
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::AirdropExpired as u32,
+        ));
+    }
+
+    if !verify_proof(&proof, &merkle_root, leaf(index, claimant.key, amount)) {
+        msg!("Invalid merkle proof");
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidMerkleProof as u32,
+        ));
+    }
+
+    let bitmap_index = index / CLAIMS_PER_BITMAP;
+    verify_claim_bitmap_pda(
+        program_id,
+        distributor_pda.key,
+        bitmap_index,
+        claim_bitmap_pda,
+    )?;
+    if *claim_bitmap_pda.owner == system_program::id() {
+        storage::create_pda(
+            program_id,
+            claimant,
+            &[
+                b"airdrop_claims",
+                distributor_pda.key.as_ref(),
+                &bitmap_index.to_le_bytes(),
+            ],
+            claim_bitmap_pda,
+            CLAIM_BITMAP_LEN,
+        )?;
+    }
+    {
+        let bit = index % CLAIMS_PER_BITMAP;
+        let mut claim_bitmap = claim_bitmap_pda.try_borrow_mut_data()?;
+        let byte = &mut claim_bitmap[(bit / 8) as usize];
+        let mask = 1 << (bit % 8);
+        if *byte & mask != 0 {
+            msg!("Airdrop {} already claimed", index);
+            return Err(ProgramError::Custom(
+                SimpleTokenErrors::AlreadyClaimed as u32,
+            ));
+        }
+        *byte |= mask;
+    }
+
+    // a root committing to more than total_amount can't pay out more than was minted
+    if amount > distributor.remaining() {
+        msg!("Airdrop exhausted");
+        return Err(ProgramError::InsufficientFunds);
+    }
+    distributor.claimed_amount += amount;
+    storage::save_state(&distributor, distributor_pda)?;
+
+    storage::verify_user_pda(program_id, &mint, claimant.key, claimant_pda)?;
+    if *claimant_pda.owner == system_program::id() {
+        storage::create_user_pda(program_id, claimant, &mint, claimant.key, claimant_pda)?;
+    }
+    storage::add(amount, claimant_pda)?;
+
+    Event::Mint {
+        mint,
+        to: *claimant.key,
+        amount,
+    }
+    .emit();
+    Ok(())
+}
+
+// whatever wasn't claimed before expiry goes to the owner
+pub fn clawback(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    merkle_root: [u8; 32],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let owner = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let distributor_pda = next_account_info(accounts_iter)?;
+    let owner_pda = next_account_info(accounts_iter)?;
+
+    storage::check_owner(owner, &mint, config_pda, program_id)?;
+    let mut distributor = Distributor::get(program_id, &mint, &merkle_root, distributor_pda)?;
+    if Clock::get()?.unix_timestamp <= distributor.expiry_ts {
+        msg!("Airdrop runs until {}", distributor.expiry_ts);
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::AirdropNotExpired as u32,
+        ));
+    }
+
+    let remaining = distributor.remaining();
+    msg!("Clawing back {} airdropped tokens", remaining);
+    distributor.claimed_amount = distributor.total_amount;
+    storage::save_state(&distributor, distributor_pda)?;
+
+    storage::verify_user_pda(program_id, &mint, owner.key, owner_pda)?;
+    if *owner_pda.owner == system_program::id() {
+        storage::create_user_pda(program_id, owner, &mint, owner.key, owner_pda)?;
+    }
+    storage::add(remaining, owner_pda)
+}
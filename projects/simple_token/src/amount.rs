@@ -0,0 +1,45 @@
+use solana_program::program_error::ProgramError;
+
+// also used off-chain, so nothing in here logs
+// raw amounts are whole units of 10^-decimals tokens, "1.5" with 2 decimals is 150
+pub fn amount_to_ui_amount(amount: u64, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let digits = format!("{:0>width$}", amount, width = decimals + 1);
+    if decimals == 0 {
+        return digits;
+    }
+
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        return whole.to_string();
+    }
+    format!("{}.{}", whole, fraction)
+}
+
+pub fn ui_amount_to_amount(ui_amount: &str, decimals: u8) -> Result<u64, ProgramError> {
+    let decimals = decimals as usize;
+    let (whole, fraction) = match ui_amount.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (ui_amount, ""),
+    };
+
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    // "1.50" is fine with a single decimal, only significant digits have to fit
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let digits = format!("{}{:0<width$}", whole, fraction, width = decimals);
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    digits
+        .parse::<u64>()
+        .map_err(|_| ProgramError::ArithmeticOverflow)
+}
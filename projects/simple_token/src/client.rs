@@ -0,0 +1,1161 @@
+// off-chain helpers to build instructions with the account order the program expects
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction as SolanaInstruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+use crate::{
+    airdrop,
+    instructions::Instruction,
+    memo,
+    permit::PermitMessage,
+    storage::{Account, Config, Role, TransferFee},
+    wrap,
+};
+
+pub fn find_config_pda(program_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"config", mint.as_ref()], program_id).0
+}
+
+pub fn find_user_pda(program_id: &Pubkey, mint: &Pubkey, user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[mint.as_ref(), user.as_ref()], program_id).0
+}
+
+pub fn find_allowance_pda(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"allowance",
+            mint.as_ref(),
+            owner.as_ref(),
+            delegate.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_fee_vault_pda(program_id: &Pubkey, mint: &Pubkey, fee_collector: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"fees", mint.as_ref(), fee_collector.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_permit_nonce_pda(program_id: &Pubkey, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"permit_nonce", mint.as_ref(), owner.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_vesting_pda(program_id: &Pubkey, mint: &Pubkey, beneficiary: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"vesting", mint.as_ref(), beneficiary.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_stake_pool_pda(program_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"stake_pool", mint.as_ref()], program_id).0
+}
+
+pub fn find_stake_pda(program_id: &Pubkey, mint: &Pubkey, staker: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"stake", mint.as_ref(), staker.as_ref()], program_id).0
+}
+
+pub fn find_distributor_pda(program_id: &Pubkey, mint: &Pubkey, merkle_root: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[b"distributor", mint.as_ref(), merkle_root], program_id).0
+}
+
+// the bitmap PDA holding the claimed bit of the recipient at index
+pub fn find_claim_bitmap_pda(program_id: &Pubkey, distributor: &Pubkey, index: u64) -> Pubkey {
+    let bitmap_index = index / airdrop::CLAIMS_PER_BITMAP;
+    Pubkey::find_program_address(
+        &[
+            b"airdrop_claims",
+            distributor.as_ref(),
+            &bitmap_index.to_le_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+// the root and one proof per leaf, a node without a sibling moves up a level unchanged
+pub fn merkle_tree(leaves: &[[u8; 32]]) -> ([u8; 32], Vec<Vec<[u8; 32]>>) {
+    let mut proofs = vec![Vec::new(); leaves.len()];
+    // the leaves each node of the current level covers
+    let mut level: Vec<([u8; 32], Vec<usize>)> = leaves
+        .iter()
+        .enumerate()
+        .map(|(index, leaf)| (*leaf, vec![index]))
+        .collect();
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [(left, left_leaves), (right, right_leaves)] => {
+                    for index in left_leaves {
+                        proofs[*index].push(*right);
+                    }
+                    for index in right_leaves {
+                        proofs[*index].push(*left);
+                    }
+                    let covered = [left_leaves.as_slice(), right_leaves.as_slice()].concat();
+                    (airdrop::hash_pair(left, right), covered)
+                }
+                _ => pair[0].clone(),
+            })
+            .collect();
+    }
+
+    let root = level.first().map(|(root, _)| *root).unwrap_or_default();
+    (root, proofs)
+}
+
+pub fn find_wrapped_mint(program_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    wrap::find_wrapped_mint(program_id, mint).0
+}
+
+pub fn find_wrap_authority(program_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    wrap::find_wrap_authority(program_id, mint).0
+}
+
+// the fee vault is only checked when a transfer fee is configured
+pub fn find_transfer_fee_vault(program_id: &Pubkey, mint: &Pubkey, config: &Config) -> Pubkey {
+    match &config.transfer_fee {
+        Some(transfer_fee) => find_fee_vault_pda(program_id, mint, &transfer_fee.fee_collector),
+        None => system_program::id(),
+    }
+}
+
+// the hook program followed by the accounts it needs, empty when no hook is configured
+pub fn transfer_hook_accounts(config: &Config, extra_accounts: &[AccountMeta]) -> Vec<AccountMeta> {
+    match config.transfer_hook_program {
+        Some(transfer_hook_program) => {
+            let mut accounts = vec![AccountMeta::new_readonly(transfer_hook_program, false)];
+            accounts.extend_from_slice(extra_accounts);
+            accounts
+        }
+        None => Vec::new(),
+    }
+}
+
+pub fn deserialize_config(data: &[u8]) -> Result<Config, ProgramError> {
+    Config::try_from_slice(data).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+pub fn deserialize_account(data: &[u8]) -> Result<Account, ProgramError> {
+    Account::try_from_slice(data).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+fn build(
+    program_id: &Pubkey,
+    instruction: Instruction,
+    accounts: Vec<AccountMeta>,
+) -> SolanaInstruction {
+    SolanaInstruction::new_with_bytes(*program_id, &instruction.try_to_vec().unwrap(), accounts)
+}
+
+// most instructions only need the signer and the config of the mint
+fn config_accounts(program_id: &Pubkey, signer: &Pubkey, mint: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*signer, true),
+        AccountMeta::new(find_config_pda(program_id, mint), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn initialize(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    decimals: u8,
+    freeze_authority: Option<Pubkey>,
+    clawback_authority: Option<Pubkey>,
+    non_transferable: bool,
+    max_supply: Option<u64>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::Initialize {
+            mint: *mint,
+            owner: *owner,
+            decimals,
+            freeze_authority,
+            clawback_authority,
+            non_transferable,
+            max_supply,
+            name,
+            symbol,
+            uri,
+        },
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*mint, true),
+            AccountMeta::new(find_config_pda(program_id, mint), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn mint(
+    program_id: &Pubkey,
+    minter: &Pubkey,
+    mint: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::Mint {
+            mint: *mint,
+            to: *to,
+            amount,
+        },
+        vec![
+            AccountMeta::new(*minter, true),
+            AccountMeta::new(find_config_pda(program_id, mint), false),
+            AccountMeta::new(find_user_pda(program_id, mint, to), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn batch_mint(
+    program_id: &Pubkey,
+    minter: &Pubkey,
+    mint: &Pubkey,
+    recipients: Vec<(Pubkey, u64)>,
+) -> SolanaInstruction {
+    let mut accounts = config_accounts(program_id, minter, mint);
+    for (to, _) in recipients.iter() {
+        accounts.push(AccountMeta::new(find_user_pda(program_id, mint, to), false));
+    }
+
+    build(
+        program_id,
+        Instruction::BatchMint {
+            mint: *mint,
+            recipients,
+        },
+        accounts,
+    )
+}
+
+fn transfer_accounts(
+    program_id: &Pubkey,
+    from: &Pubkey,
+    mint: &Pubkey,
+    to: &Pubkey,
+    fee_vault: &Pubkey,
+    transfer_hook_accounts: &[AccountMeta],
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new(*from, true),
+        AccountMeta::new(find_config_pda(program_id, mint), false),
+        AccountMeta::new(find_user_pda(program_id, mint, from), false),
+        AccountMeta::new(find_user_pda(program_id, mint, to), false),
+        AccountMeta::new(*fee_vault, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend_from_slice(transfer_hook_accounts);
+    accounts
+}
+
+// fee_vault is the vault of the configured fee collector, see find_transfer_fee_vault,
+// transfer_hook_accounts are appended as they are, see transfer_hook_accounts
+pub fn transfer(
+    program_id: &Pubkey,
+    from: &Pubkey,
+    mint: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+    fee_vault: &Pubkey,
+    transfer_hook_accounts: &[AccountMeta],
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::Transfer {
+            mint: *mint,
+            to: *to,
+            amount,
+        },
+        transfer_accounts(
+            program_id,
+            from,
+            mint,
+            to,
+            fee_vault,
+            transfer_hook_accounts,
+        ),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked(
+    program_id: &Pubkey,
+    from: &Pubkey,
+    mint: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    fee_vault: &Pubkey,
+    transfer_hook_accounts: &[AccountMeta],
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::TransferChecked {
+            mint: *mint,
+            to: *to,
+            amount,
+            decimals,
+        },
+        transfer_accounts(
+            program_id,
+            from,
+            mint,
+            to,
+            fee_vault,
+            transfer_hook_accounts,
+        ),
+    )
+}
+
+// transfer's accounts with the instructions sysvar inserted after the system program
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_with_memo(
+    program_id: &Pubkey,
+    from: &Pubkey,
+    mint: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+    memo: String,
+    fee_vault: &Pubkey,
+    transfer_hook_accounts: &[AccountMeta],
+) -> SolanaInstruction {
+    let mut accounts = transfer_accounts(
+        program_id,
+        from,
+        mint,
+        to,
+        fee_vault,
+        transfer_hook_accounts,
+    );
+    accounts.insert(
+        memo::INSTRUCTIONS_SYSVAR_INDEX,
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+    );
+
+    build(
+        program_id,
+        Instruction::TransferWithMemo {
+            mint: *mint,
+            to: *to,
+            amount,
+            memo,
+        },
+        accounts,
+    )
+}
+
+// the SPL Memo instruction a mint with memo_required expects right before TransferWithMemo
+pub fn memo(memo: &str) -> SolanaInstruction {
+    SolanaInstruction::new_with_bytes(memo::MEMO_PROGRAM_ID, memo.as_bytes(), vec![])
+}
+
+pub fn batch_transfer(
+    program_id: &Pubkey,
+    from: &Pubkey,
+    mint: &Pubkey,
+    recipients: Vec<(Pubkey, u64)>,
+    fee_vault: &Pubkey,
+    transfer_hook_accounts: &[AccountMeta],
+) -> SolanaInstruction {
+    let mut accounts = vec![
+        AccountMeta::new(*from, true),
+        AccountMeta::new(find_config_pda(program_id, mint), false),
+        AccountMeta::new(find_user_pda(program_id, mint, from), false),
+        AccountMeta::new(*fee_vault, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    for (to, _) in recipients.iter() {
+        accounts.push(AccountMeta::new(find_user_pda(program_id, mint, to), false));
+    }
+    accounts.extend_from_slice(transfer_hook_accounts);
+
+    build(
+        program_id,
+        Instruction::BatchTransfer {
+            mint: *mint,
+            recipients,
+        },
+        accounts,
+    )
+}
+
+pub fn burn(
+    program_id: &Pubkey,
+    burner: &Pubkey,
+    mint: &Pubkey,
+    from: &Pubkey,
+    amount: u64,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::Burn {
+            mint: *mint,
+            from: *from,
+            amount,
+        },
+        vec![
+            AccountMeta::new(*burner, true),
+            AccountMeta::new(find_config_pda(program_id, mint), false),
+            AccountMeta::new(find_user_pda(program_id, mint, from), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn burn_own(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::BurnOwn {
+            mint: *mint,
+            amount,
+        },
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new(find_config_pda(program_id, mint), false),
+            AccountMeta::new(find_user_pda(program_id, mint, holder), false),
+        ],
+    )
+}
+
+pub fn close_account(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::CloseAccount { mint: *mint },
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new(find_user_pda(program_id, mint, holder), false),
+            AccountMeta::new(*destination, false),
+        ],
+    )
+}
+
+pub fn approve(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    delegate: &Pubkey,
+    amount: u64,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::Approve {
+            mint: *mint,
+            delegate: *delegate,
+            amount,
+        },
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(find_allowance_pda(program_id, mint, owner, delegate), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn revoke(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    delegate: &Pubkey,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::Revoke {
+            mint: *mint,
+            delegate: *delegate,
+        },
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(find_allowance_pda(program_id, mint, owner, delegate), false),
+        ],
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_from(
+    program_id: &Pubkey,
+    delegate: &Pubkey,
+    mint: &Pubkey,
+    from: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+    fee_vault: &Pubkey,
+    transfer_hook_accounts: &[AccountMeta],
+) -> SolanaInstruction {
+    let mut accounts = vec![
+        AccountMeta::new(*delegate, true),
+        AccountMeta::new(find_config_pda(program_id, mint), false),
+        AccountMeta::new(find_allowance_pda(program_id, mint, from, delegate), false),
+        AccountMeta::new(find_user_pda(program_id, mint, from), false),
+        AccountMeta::new(find_user_pda(program_id, mint, to), false),
+        AccountMeta::new(*fee_vault, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend_from_slice(transfer_hook_accounts);
+
+    build(
+        program_id,
+        Instruction::TransferFrom {
+            mint: *mint,
+            from: *from,
+            to: *to,
+            amount,
+        },
+        accounts,
+    )
+}
+
+fn set_frozen_accounts(
+    program_id: &Pubkey,
+    freeze_authority: &Pubkey,
+    mint: &Pubkey,
+    user: &Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*freeze_authority, true),
+        AccountMeta::new(find_config_pda(program_id, mint), false),
+        AccountMeta::new(find_user_pda(program_id, mint, user), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]
+}
+
+pub fn freeze_account(
+    program_id: &Pubkey,
+    freeze_authority: &Pubkey,
+    mint: &Pubkey,
+    user: &Pubkey,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::FreezeAccount {
+            mint: *mint,
+            user: *user,
+        },
+        set_frozen_accounts(program_id, freeze_authority, mint, user),
+    )
+}
+
+pub fn thaw_account(
+    program_id: &Pubkey,
+    freeze_authority: &Pubkey,
+    mint: &Pubkey,
+    user: &Pubkey,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::ThawAccount {
+            mint: *mint,
+            user: *user,
+        },
+        set_frozen_accounts(program_id, freeze_authority, mint, user),
+    )
+}
+
+pub fn force_transfer(
+    program_id: &Pubkey,
+    clawback_authority: &Pubkey,
+    mint: &Pubkey,
+    from: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::ForceTransfer {
+            mint: *mint,
+            from: *from,
+            to: *to,
+            amount,
+        },
+        vec![
+            AccountMeta::new(*clawback_authority, true),
+            AccountMeta::new_readonly(find_config_pda(program_id, mint), false),
+            AccountMeta::new(find_user_pda(program_id, mint, from), false),
+            AccountMeta::new(find_user_pda(program_id, mint, to), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn update_metadata(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    mint: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::UpdateMetadata {
+            mint: *mint,
+            name,
+            symbol,
+            uri,
+        },
+        config_accounts(program_id, admin, mint),
+    )
+}
+
+// the supply is returned as a borsh encoded Supply in the return data
+pub fn get_supply(program_id: &Pubkey, mint: &Pubkey) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::GetSupply { mint: *mint },
+        vec![AccountMeta::new_readonly(
+            find_config_pda(program_id, mint),
+            false,
+        )],
+    )
+}
+
+// the balance is returned as a borsh encoded u64 in the return data
+pub fn balance_at(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    user: &Pubkey,
+    slot: u64,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::BalanceAt {
+            mint: *mint,
+            user: *user,
+            slot,
+        },
+        vec![AccountMeta::new_readonly(
+            find_user_pda(program_id, mint, user),
+            false,
+        )],
+    )
+}
+
+// the message the owner has to sign, sent in an ed25519 instruction right before permit
+pub fn permit_message(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+    amount: u64,
+    nonce: u64,
+    expiry: i64,
+) -> Vec<u8> {
+    PermitMessage {
+        program_id: *program_id,
+        mint: *mint,
+        owner: *owner,
+        delegate: *delegate,
+        amount,
+        nonce,
+        expiry,
+    }
+    .try_to_vec()
+    .unwrap()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn permit(
+    program_id: &Pubkey,
+    relayer: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+    amount: u64,
+    nonce: u64,
+    expiry: i64,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::Permit {
+            mint: *mint,
+            owner: *owner,
+            delegate: *delegate,
+            amount,
+            nonce,
+            expiry,
+        },
+        vec![
+            AccountMeta::new(*relayer, true),
+            AccountMeta::new(find_permit_nonce_pda(program_id, mint, owner), false),
+            AccountMeta::new(find_allowance_pda(program_id, mint, owner, delegate), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn initialize_wrapped_mint(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::InitializeWrappedMint { mint: *mint },
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(find_config_pda(program_id, mint), false),
+            AccountMeta::new(find_wrapped_mint(program_id, mint), false),
+            AccountMeta::new_readonly(find_wrap_authority(program_id, mint), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+// token_account is an SPL token account of the wrapped mint receiving the wrapped tokens
+pub fn wrap(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    amount: u64,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::Wrap {
+            mint: *mint,
+            amount,
+        },
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new(find_config_pda(program_id, mint), false),
+            AccountMeta::new(find_user_pda(program_id, mint, holder), false),
+            AccountMeta::new(find_wrapped_mint(program_id, mint), false),
+            AccountMeta::new_readonly(find_wrap_authority(program_id, mint), false),
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+    )
+}
+
+// token_account is the holder's SPL token account the wrapped tokens are burned from
+pub fn unwrap(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    amount: u64,
+    transfer_hook_accounts: &[AccountMeta],
+) -> SolanaInstruction {
+    let mut accounts = vec![
+        AccountMeta::new(*holder, true),
+        AccountMeta::new(find_config_pda(program_id, mint), false),
+        AccountMeta::new(find_user_pda(program_id, mint, holder), false),
+        AccountMeta::new(find_wrapped_mint(program_id, mint), false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend_from_slice(transfer_hook_accounts);
+
+    build(
+        program_id,
+        Instruction::Unwrap {
+            mint: *mint,
+            amount,
+        },
+        accounts,
+    )
+}
+
+pub fn set_transfer_fee(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    mint: &Pubkey,
+    transfer_fee: Option<TransferFee>,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::SetTransferFee {
+            mint: *mint,
+            transfer_fee,
+        },
+        config_accounts(program_id, admin, mint),
+    )
+}
+
+pub fn set_transfer_hook(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    mint: &Pubkey,
+    transfer_hook_program: Option<Pubkey>,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::SetTransferHook {
+            mint: *mint,
+            transfer_hook_program,
+        },
+        config_accounts(program_id, admin, mint),
+    )
+}
+
+pub fn set_memo_required(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    mint: &Pubkey,
+    required: bool,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::SetMemoRequired {
+            mint: *mint,
+            required,
+        },
+        config_accounts(program_id, admin, mint),
+    )
+}
+
+pub fn withdraw_fees(
+    program_id: &Pubkey,
+    fee_collector: &Pubkey,
+    mint: &Pubkey,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::WithdrawFees { mint: *mint },
+        vec![
+            AccountMeta::new(*fee_collector, true),
+            AccountMeta::new(find_fee_vault_pda(program_id, mint, fee_collector), false),
+            AccountMeta::new(find_user_pda(program_id, mint, fee_collector), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn propose_owner(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    new_owner: &Pubkey,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::ProposeOwner {
+            mint: *mint,
+            new_owner: *new_owner,
+        },
+        config_accounts(program_id, owner, mint),
+    )
+}
+
+pub fn accept_ownership(
+    program_id: &Pubkey,
+    new_owner: &Pubkey,
+    mint: &Pubkey,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::AcceptOwnership { mint: *mint },
+        config_accounts(program_id, new_owner, mint),
+    )
+}
+
+pub fn cancel_ownership_transfer(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::CancelOwnershipTransfer { mint: *mint },
+        config_accounts(program_id, owner, mint),
+    )
+}
+
+pub fn grant_role(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    role: Role,
+    holder: &Pubkey,
+    mint_quota: Option<u64>,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::GrantRole {
+            mint: *mint,
+            role,
+            holder: *holder,
+            mint_quota,
+        },
+        config_accounts(program_id, authority, mint),
+    )
+}
+
+pub fn revoke_role(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    role: Role,
+    holder: &Pubkey,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::RevokeRole {
+            mint: *mint,
+            role,
+            holder: *holder,
+        },
+        config_accounts(program_id, authority, mint),
+    )
+}
+
+pub fn pause(program_id: &Pubkey, pauser: &Pubkey, mint: &Pubkey) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::Pause { mint: *mint },
+        config_accounts(program_id, pauser, mint),
+    )
+}
+
+pub fn unpause(program_id: &Pubkey, pauser: &Pubkey, mint: &Pubkey) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::Unpause { mint: *mint },
+        config_accounts(program_id, pauser, mint),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn mint_vested(
+    program_id: &Pubkey,
+    minter: &Pubkey,
+    mint: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+    start_ts: i64,
+    cliff_ts: i64,
+    end_ts: i64,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::MintVested {
+            mint: *mint,
+            to: *to,
+            amount,
+            start_ts,
+            cliff_ts,
+            end_ts,
+        },
+        vec![
+            AccountMeta::new(*minter, true),
+            AccountMeta::new(find_config_pda(program_id, mint), false),
+            AccountMeta::new(find_vesting_pda(program_id, mint, to), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn claim_vested(program_id: &Pubkey, beneficiary: &Pubkey, mint: &Pubkey) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::ClaimVested { mint: *mint },
+        vec![
+            AccountMeta::new(*beneficiary, true),
+            AccountMeta::new_readonly(find_config_pda(program_id, mint), false),
+            AccountMeta::new(find_vesting_pda(program_id, mint, beneficiary), false),
+            AccountMeta::new(find_user_pda(program_id, mint, beneficiary), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn fund_staking_rewards(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    reward_rate: u64,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::FundStakingRewards {
+            mint: *mint,
+            amount,
+            reward_rate,
+        },
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(find_config_pda(program_id, mint), false),
+            AccountMeta::new(find_user_pda(program_id, mint, owner), false),
+            AccountMeta::new(find_stake_pool_pda(program_id, mint), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+// Stake, Unstake and ClaimRewards all take the same accounts
+fn staking_accounts(program_id: &Pubkey, staker: &Pubkey, mint: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*staker, true),
+        AccountMeta::new(find_config_pda(program_id, mint), false),
+        AccountMeta::new(find_user_pda(program_id, mint, staker), false),
+        AccountMeta::new(find_stake_pool_pda(program_id, mint), false),
+        AccountMeta::new(find_stake_pda(program_id, mint, staker), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]
+}
+
+pub fn stake(
+    program_id: &Pubkey,
+    staker: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::Stake {
+            mint: *mint,
+            amount,
+        },
+        staking_accounts(program_id, staker, mint),
+    )
+}
+
+pub fn unstake(
+    program_id: &Pubkey,
+    staker: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::Unstake {
+            mint: *mint,
+            amount,
+        },
+        staking_accounts(program_id, staker, mint),
+    )
+}
+
+pub fn claim_rewards(program_id: &Pubkey, staker: &Pubkey, mint: &Pubkey) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::ClaimRewards { mint: *mint },
+        staking_accounts(program_id, staker, mint),
+    )
+}
+
+pub fn create_airdrop(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    expiry_ts: i64,
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::CreateAirdrop {
+            mint: *mint,
+            merkle_root,
+            total_amount,
+            expiry_ts,
+        },
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(find_config_pda(program_id, mint), false),
+            AccountMeta::new(find_distributor_pda(program_id, mint, &merkle_root), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn claim_airdrop(
+    program_id: &Pubkey,
+    claimant: &Pubkey,
+    mint: &Pubkey,
+    merkle_root: [u8; 32],
+    index: u64,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> SolanaInstruction {
+    let distributor = find_distributor_pda(program_id, mint, &merkle_root);
+    build(
+        program_id,
+        Instruction::ClaimAirdrop {
+            mint: *mint,
+            merkle_root,
+            index,
+            amount,
+            proof,
+        },
+        vec![
+            AccountMeta::new(*claimant, true),
+            AccountMeta::new_readonly(find_config_pda(program_id, mint), false),
+            AccountMeta::new(distributor, false),
+            AccountMeta::new(
+                find_claim_bitmap_pda(program_id, &distributor, index),
+                false,
+            ),
+            AccountMeta::new(find_user_pda(program_id, mint, claimant), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn clawback_airdrop(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    merkle_root: [u8; 32],
+) -> SolanaInstruction {
+    build(
+        program_id,
+        Instruction::ClawbackAirdrop {
+            mint: *mint,
+            merkle_root,
+        },
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(find_config_pda(program_id, mint), false),
+            AccountMeta::new(find_distributor_pda(program_id, mint, &merkle_root), false),
+            AccountMeta::new(find_user_pda(program_id, mint, owner), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
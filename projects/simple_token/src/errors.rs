@@ -3,5 +3,33 @@ pub enum SimpleTokenErrors {
     InvalidTo = 2,
     InvalidFrom = 3,
     InvalidSigner = 4,
-    InvalidOwner = 5
+    InvalidOwner = 5,
+    InsufficientAllowance = 6,
+    AccountFrozen = 7,
+    InvalidFreezeAuthority = 8,
+    MetadataTooLong = 9,
+    MaxSupplyExceeded = 10,
+    InvalidMint = 11,
+    NonZeroBalance = 12,
+    InvalidTransferFee = 13,
+    InvalidFeeCollector = 14,
+    InvalidPendingOwner = 15,
+    MintQuotaExceeded = 16,
+    Paused = 17,
+    InvalidVestingSchedule = 18,
+    CheckpointUnavailable = 19,
+    PermitExpired = 20,
+    InvalidPermitNonce = 21,
+    InvalidPermitSignature = 22,
+    InvalidTransferHook = 23,
+    InvalidDecimals = 24,
+    MemoTooLong = 25,
+    MissingMemo = 26,
+    InsufficientStake = 27,
+    InvalidMerkleProof = 28,
+    AlreadyClaimed = 29,
+    AirdropExpired = 30,
+    AirdropNotExpired = 31,
+    InvalidClawbackAuthority = 32,
+    NonTransferable = 33
 }
\ No newline at end of file
@@ -0,0 +1,108 @@
+#[cfg(feature = "client")]
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
+
+// first data field of every event so they can be told apart from other programs' data logs
+pub const EVENT_TAG: &[u8] = b"simple_token:event";
+
+#[cfg(feature = "client")]
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq)]
+pub enum Event {
+    Initialize {
+        mint: Pubkey,
+        owner: Pubkey,
+        decimals: u8,
+    },
+    Mint {
+        mint: Pubkey,
+        to: Pubkey,
+        amount: u64,
+    },
+    Transfer {
+        mint: Pubkey,
+        from: Pubkey,
+        to: Pubkey,
+        amount: u64,
+        fee: u64,
+    },
+    Burn {
+        mint: Pubkey,
+        from: Pubkey,
+        amount: u64,
+    },
+    ChangeOwner {
+        mint: Pubkey,
+        previous_owner: Pubkey,
+        new_owner: Pubkey,
+    },
+    // audit trail of the clawback authority
+    ForceTransfer {
+        mint: Pubkey,
+        authority: Pubkey,
+        from: Pubkey,
+        to: Pubkey,
+        amount: u64,
+    },
+    // follows the Transfer event of a TransferWithMemo
+    Memo {
+        mint: Pubkey,
+        from: Pubkey,
+        to: Pubkey,
+        memo: String,
+    },
+}
+
+impl Event {
+    pub fn emit(&self) {
+        // borsh serialization into a Vec can't fail
+        let data = self.try_to_vec().unwrap();
+        sol_log_data(&[EVENT_TAG, &data]);
+    }
+}
+
+/// Decodes the events emitted by `program_id` from the log messages of a transaction.
+/// Data logged by other programs, including CPIs made by simple_token, is skipped.
+#[cfg(feature = "client")]
+pub fn decode_events(program_id: &Pubkey, log_messages: &[String]) -> Vec<Event> {
+    // whether each program on the current invoke stack is simple_token
+    let mut invoke_stack: Vec<bool> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in log_messages {
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if invoke_stack.last() == Some(&true) {
+                events.extend(decode_event(data));
+            }
+            continue;
+        }
+
+        let words: Vec<&str> = log.split(' ').collect();
+        let invoked_program = match words.get(1).map(|word| word.parse::<Pubkey>()) {
+            Some(Ok(invoked_program)) if words[0] == "Program" => invoked_program,
+            _ => continue,
+        };
+        match words.get(2) {
+            Some(&"invoke") => invoke_stack.push(invoked_program == *program_id),
+            Some(&"success") | Some(&"failed:") => {
+                invoke_stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
+
+#[cfg(feature = "client")]
+fn decode_event(data: &str) -> Option<Event> {
+    let mut fields = data.split(' ').map(|field| STANDARD.decode(field));
+    match (fields.next(), fields.next(), fields.next()) {
+        (Some(Ok(tag)), Some(Ok(event)), None) if tag == EVENT_TAG => {
+            Event::try_from_slice(&event).ok()
+        }
+        _ => None,
+    }
+}
@@ -1,26 +1,217 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::pubkey::Pubkey;
 
+use crate::storage::{Role, TransferFee};
+
 
 #[derive(BorshDeserialize, BorshSerialize, Debug)]
 pub enum Instruction {
     Mint {
+        mint: Pubkey,
         to: Pubkey,
         amount: u64,
     },
     Transfer {
+        mint: Pubkey,
         to: Pubkey,
         amount: u64,
     },
     Burn {
+        mint: Pubkey,
         from: Pubkey,
         amount: u64,
     },
-    ChangeOwner {
+    ProposeOwner {
+        mint: Pubkey,
         new_owner: Pubkey,
     },
     Initialize {
+        mint: Pubkey,
+        owner: Pubkey,
+        decimals: u8,
+        freeze_authority: Option<Pubkey>,
+        clawback_authority: Option<Pubkey>,
+        non_transferable: bool,
+        max_supply: Option<u64>,
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    Approve {
+        mint: Pubkey,
+        delegate: Pubkey,
+        amount: u64,
+    },
+    TransferFrom {
+        mint: Pubkey,
+        from: Pubkey,
+        to: Pubkey,
+        amount: u64,
+    },
+    Revoke {
+        mint: Pubkey,
+        delegate: Pubkey,
+    },
+    FreezeAccount {
+        mint: Pubkey,
+        user: Pubkey,
+    },
+    ThawAccount {
+        mint: Pubkey,
+        user: Pubkey,
+    },
+    UpdateMetadata {
+        mint: Pubkey,
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    GetSupply {
+        mint: Pubkey,
+    },
+    BurnOwn {
+        mint: Pubkey,
+        amount: u64,
+    },
+    CloseAccount {
+        mint: Pubkey,
+    },
+    SetTransferFee {
+        mint: Pubkey,
+        transfer_fee: Option<TransferFee>,
+    },
+    WithdrawFees {
+        mint: Pubkey,
+    },
+    BatchTransfer {
+        mint: Pubkey,
+        recipients: Vec<(Pubkey, u64)>,
+    },
+    BatchMint {
+        mint: Pubkey,
+        recipients: Vec<(Pubkey, u64)>,
+    },
+    AcceptOwnership {
+        mint: Pubkey,
+    },
+    CancelOwnershipTransfer {
+        mint: Pubkey,
+    },
+    GrantRole {
+        mint: Pubkey,
+        role: Role,
+        holder: Pubkey,
+        mint_quota: Option<u64>,
+    },
+    RevokeRole {
+        mint: Pubkey,
+        role: Role,
+        holder: Pubkey,
+    },
+    Pause {
+        mint: Pubkey,
+    },
+    Unpause {
+        mint: Pubkey,
+    },
+    MintVested {
+        mint: Pubkey,
+        to: Pubkey,
+        amount: u64,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+    },
+    ClaimVested {
+        mint: Pubkey,
+    },
+    BalanceAt {
+        mint: Pubkey,
+        user: Pubkey,
+        slot: u64,
+    },
+    Permit {
+        mint: Pubkey,
         owner: Pubkey,
+        delegate: Pubkey,
+        amount: u64,
+        nonce: u64,
+        expiry: i64,
+    },
+    InitializeWrappedMint {
+        mint: Pubkey,
+    },
+    Wrap {
+        mint: Pubkey,
+        amount: u64,
+    },
+    Unwrap {
+        mint: Pubkey,
+        amount: u64,
+    },
+    SetTransferHook {
+        mint: Pubkey,
+        transfer_hook_program: Option<Pubkey>,
+    },
+    TransferChecked {
+        mint: Pubkey,
+        to: Pubkey,
+        amount: u64,
         decimals: u8,
     },
+    TransferWithMemo {
+        mint: Pubkey,
+        to: Pubkey,
+        amount: u64,
+        memo: String,
+    },
+    SetMemoRequired {
+        mint: Pubkey,
+        required: bool,
+    },
+    FundStakingRewards {
+        mint: Pubkey,
+        amount: u64,
+        reward_rate: u64,
+    },
+    Stake {
+        mint: Pubkey,
+        amount: u64,
+    },
+    Unstake {
+        mint: Pubkey,
+        amount: u64,
+    },
+    ClaimRewards {
+        mint: Pubkey,
+    },
+    CreateAirdrop {
+        mint: Pubkey,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        expiry_ts: i64,
+    },
+    ClaimAirdrop {
+        mint: Pubkey,
+        merkle_root: [u8; 32],
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    },
+    ClawbackAirdrop {
+        mint: Pubkey,
+        merkle_root: [u8; 32],
+    },
+    ForceTransfer {
+        mint: Pubkey,
+        from: Pubkey,
+        to: Pubkey,
+        amount: u64,
+    },
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct Supply {
+    pub total_supply: u64,
+    pub max_supply: Option<u64>,
 }
\ No newline at end of file
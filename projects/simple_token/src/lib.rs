@@ -1,24 +1,44 @@
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use errors::SimpleTokenErrors;
-use instructions::Instruction;
+use events::Event;
+use instructions::{Instruction, Supply};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint,
     entrypoint::ProgramResult,
     msg,
+    program::set_return_data,
     program_error::ProgramError,
     pubkey::Pubkey,
     system_program,
 };
 use storage::{
-    add, check_owner, create_user_pda, initialize_config, remove, update_owner, verify_user_pda,
+    accept_ownership, add, balance_at, check_clawback_authority, check_decimals,
+    check_freeze_authority, check_not_paused, check_owner, check_role, check_transferable,
+    close_user_pda, consume_mint_quota, create_allowance_pda, create_fee_vault_pda,
+    create_user_pda, decrease_supply, force_remove, get_transfer_fee, grant_role, increase_supply,
+    initialize_config, remove, revoke_role, set_allowance, set_frozen, set_memo_required,
+    set_paused, set_pending_owner, set_transfer_fee, set_transfer_hook, spend_allowance,
+    update_metadata, verify_allowance_pda, verify_fee_vault_pda, verify_user_pda, withdraw_fees,
+    Config, Role, TransferFee,
 };
 
 use crate::storage::check_config_pda;
 
+pub mod airdrop;
+pub mod amount;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod memo;
+pub mod permit;
+pub mod staking;
 pub mod storage;
+pub mod transfer_hook;
+pub mod vesting;
+pub mod wrap;
 
 entrypoint!(process_instruction);
 
@@ -30,74 +50,351 @@ pub fn process_instruction(
     let instruction = Instruction::try_from_slice(instruction_data)?;
     msg!("Instruction: {:?}", instruction);
     return match instruction {
-        instructions::Instruction::Mint {  to, amount } => {
-            self::mint(programm_id, accounts, to, amount)
+        instructions::Instruction::Mint { mint, to, amount } => {
+            self::mint(programm_id, accounts, mint, to, amount)
         }
-        instructions::Instruction::Transfer {  to, amount } => {
-            self::transfer(programm_id, accounts, to, amount)
+        instructions::Instruction::Transfer { mint, to, amount } => {
+            self::transfer(programm_id, accounts, mint, to, amount, false)
         }
-        instructions::Instruction::Burn { from, amount } => {
-            self::burn(programm_id, accounts, from, amount)
+        instructions::Instruction::Burn { mint, from, amount } => {
+            self::burn(programm_id, accounts, mint, from, amount)
         }
 
-        instructions::Instruction::ChangeOwner { new_owner } => {
-            self::change_owner(programm_id, accounts, new_owner)
+        instructions::Instruction::ProposeOwner { mint, new_owner } => {
+            self::propose_owner(programm_id, accounts, mint, new_owner)
         }
-        instructions::Instruction::Initialize { owner, decimals } => {
-            self::initialize(programm_id, accounts, owner, decimals)
+        instructions::Instruction::Initialize {
+            mint,
+            owner,
+            decimals,
+            freeze_authority,
+            clawback_authority,
+            non_transferable,
+            max_supply,
+            name,
+            symbol,
+            uri,
+        } => self::initialize(
+            programm_id,
+            accounts,
+            mint,
+            Config {
+                owner,
+                pending_owner: None,
+                roles: Vec::new(),
+                paused: false,
+                decimals,
+                freeze_authority,
+                clawback_authority,
+                non_transferable,
+                total_supply: 0,
+                max_supply,
+                transfer_fee: None,
+                transfer_hook_program: None,
+                memo_required: false,
+                name,
+                symbol,
+                uri,
+            },
+        ),
+        instructions::Instruction::Approve {
+            mint,
+            delegate,
+            amount,
+        } => self::approve(programm_id, accounts, mint, delegate, amount),
+        instructions::Instruction::TransferFrom {
+            mint,
+            from,
+            to,
+            amount,
+        } => self::transfer_from(programm_id, accounts, mint, from, to, amount),
+        instructions::Instruction::Revoke { mint, delegate } => {
+            self::revoke(programm_id, accounts, mint, delegate)
         }
+        instructions::Instruction::FreezeAccount { mint, user } => {
+            self::set_account_frozen(programm_id, accounts, mint, user, true)
+        }
+        instructions::Instruction::ThawAccount { mint, user } => {
+            self::set_account_frozen(programm_id, accounts, mint, user, false)
+        }
+        instructions::Instruction::UpdateMetadata {
+            mint,
+            name,
+            symbol,
+            uri,
+        } => self::update_token_metadata(programm_id, accounts, mint, name, symbol, uri),
+        instructions::Instruction::GetSupply { mint } => {
+            self::get_supply(programm_id, accounts, mint)
+        }
+        instructions::Instruction::BurnOwn { mint, amount } => {
+            self::burn_own(programm_id, accounts, mint, amount)
+        }
+        instructions::Instruction::CloseAccount { mint } => {
+            self::close_account(programm_id, accounts, mint)
+        }
+        instructions::Instruction::SetTransferFee { mint, transfer_fee } => {
+            self::set_token_transfer_fee(programm_id, accounts, mint, transfer_fee)
+        }
+        instructions::Instruction::WithdrawFees { mint } => {
+            self::withdraw_token_fees(programm_id, accounts, mint)
+        }
+        instructions::Instruction::BatchTransfer { mint, recipients } => {
+            self::batch_transfer(programm_id, accounts, mint, recipients)
+        }
+        instructions::Instruction::BatchMint { mint, recipients } => {
+            self::batch_mint(programm_id, accounts, mint, recipients)
+        }
+        instructions::Instruction::AcceptOwnership { mint } => {
+            self::accept_owner(programm_id, accounts, mint)
+        }
+        instructions::Instruction::CancelOwnershipTransfer { mint } => {
+            self::cancel_owner_proposal(programm_id, accounts, mint)
+        }
+        instructions::Instruction::GrantRole {
+            mint,
+            role,
+            holder,
+            mint_quota,
+        } => self::grant_token_role(programm_id, accounts, mint, role, holder, mint_quota),
+        instructions::Instruction::RevokeRole { mint, role, holder } => {
+            self::revoke_token_role(programm_id, accounts, mint, role, holder)
+        }
+        instructions::Instruction::Pause { mint } => {
+            self::set_token_paused(programm_id, accounts, mint, true)
+        }
+        instructions::Instruction::Unpause { mint } => {
+            self::set_token_paused(programm_id, accounts, mint, false)
+        }
+        instructions::Instruction::MintVested {
+            mint,
+            to,
+            amount,
+            start_ts,
+            cliff_ts,
+            end_ts,
+        } => vesting::mint_vested(
+            programm_id,
+            accounts,
+            mint,
+            to,
+            amount,
+            start_ts,
+            cliff_ts,
+            end_ts,
+        ),
+        instructions::Instruction::ClaimVested { mint } => {
+            vesting::claim(programm_id, accounts, mint)
+        }
+        instructions::Instruction::BalanceAt { mint, user, slot } => {
+            self::get_balance_at(programm_id, accounts, mint, user, slot)
+        }
+        instructions::Instruction::Permit {
+            mint,
+            owner,
+            delegate,
+            amount,
+            nonce,
+            expiry,
+        } => permit::permit(
+            programm_id,
+            accounts,
+            mint,
+            owner,
+            delegate,
+            amount,
+            nonce,
+            expiry,
+        ),
+        instructions::Instruction::InitializeWrappedMint { mint } => {
+            wrap::initialize_wrapped_mint(programm_id, accounts, mint)
+        }
+        instructions::Instruction::Wrap { mint, amount } => {
+            wrap::wrap(programm_id, accounts, mint, amount)
+        }
+        instructions::Instruction::Unwrap { mint, amount } => {
+            wrap::unwrap(programm_id, accounts, mint, amount)
+        }
+        instructions::Instruction::SetTransferHook {
+            mint,
+            transfer_hook_program,
+        } => self::set_token_transfer_hook(programm_id, accounts, mint, transfer_hook_program),
+        instructions::Instruction::TransferChecked {
+            mint,
+            to,
+            amount,
+            decimals,
+        } => self::transfer_checked(programm_id, accounts, mint, to, amount, decimals),
+        instructions::Instruction::TransferWithMemo {
+            mint,
+            to,
+            amount,
+            memo,
+        } => self::transfer_with_memo(programm_id, accounts, mint, to, amount, memo),
+        instructions::Instruction::SetMemoRequired { mint, required } => {
+            self::set_token_memo_required(programm_id, accounts, mint, required)
+        }
+        instructions::Instruction::FundStakingRewards {
+            mint,
+            amount,
+            reward_rate,
+        } => staking::fund_rewards(programm_id, accounts, mint, amount, reward_rate),
+        instructions::Instruction::Stake { mint, amount } => {
+            staking::stake(programm_id, accounts, mint, amount)
+        }
+        instructions::Instruction::Unstake { mint, amount } => {
+            staking::unstake(programm_id, accounts, mint, amount)
+        }
+        instructions::Instruction::ClaimRewards { mint } => {
+            staking::claim_rewards(programm_id, accounts, mint)
+        }
+        instructions::Instruction::CreateAirdrop {
+            mint,
+            merkle_root,
+            total_amount,
+            expiry_ts,
+        } => airdrop::create_airdrop(
+            programm_id,
+            accounts,
+            mint,
+            merkle_root,
+            total_amount,
+            expiry_ts,
+        ),
+        instructions::Instruction::ClaimAirdrop {
+            mint,
+            merkle_root,
+            index,
+            amount,
+            proof,
+        } => airdrop::claim(
+            programm_id,
+            accounts,
+            mint,
+            merkle_root,
+            index,
+            amount,
+            proof,
+        ),
+        instructions::Instruction::ClawbackAirdrop { mint, merkle_root } => {
+            airdrop::clawback(programm_id, accounts, mint, merkle_root)
+        }
+        instructions::Instruction::ForceTransfer {
+            mint,
+            from,
+            to,
+            amount,
+        } => self::force_transfer(programm_id, accounts, mint, from, to, amount),
     };
 }
 
 fn initialize(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    owner: Pubkey,
-    decimals: u8,
+    mint: Pubkey,
+    config: Config,
 ) -> ProgramResult {
     msg!("Initialize");
     let accounts_iter = &mut accounts.into_iter();
     let owner_info = next_account_info(accounts_iter).unwrap();
+    let mint_info = next_account_info(accounts_iter)?;
     let config_pda = next_account_info(accounts_iter).unwrap();
-    check_config_pda(program_id, config_pda)?;
-    initialize_config(program_id, &owner_info, &owner, decimals, config_pda)?;
 
+    // the mint has to sign so nobody can squat a mint identifier before its creator
+    if *mint_info.key != mint || !mint_info.is_signer {
+        return Err(ProgramError::Custom(SimpleTokenErrors::InvalidMint as u32));
+    }
+
+    check_config_pda(program_id, &mint, config_pda)?;
+    initialize_config(program_id, &owner_info, &mint, &config, config_pda)?;
+
+    Event::Initialize {
+        mint,
+        owner: config.owner,
+        decimals: config.decimals,
+    }
+    .emit();
     Ok(())
 }
 
 fn mint<'a>(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
+    mint: Pubkey,
     to: Pubkey,
     amount: u64,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.into_iter();
-    let owner = next_account_info(accounts_iter).unwrap();
+    let minter = next_account_info(accounts_iter).unwrap();
     let config_pda = next_account_info(accounts_iter).unwrap();
-    check_owner(owner, config_pda, program_id)?;
+    check_role(minter, Role::Minter, &mint, config_pda, program_id)?;
+    check_not_paused(config_pda)?;
 
     let to_pda = next_account_info(accounts_iter).unwrap();
 
-    verify_user_pda(program_id, &to, to_pda)?;
+    verify_user_pda(program_id, &mint, &to, to_pda)?;
     if *to_pda.owner == system_program::id() {
-        create_user_pda(program_id, owner, &to, to_pda)?;
+        create_user_pda(program_id, minter, &mint, &to, to_pda)?;
     }
 
+    consume_mint_quota(minter.key, amount, config_pda)?;
+    increase_supply(amount, config_pda)?;
     add(amount, to_pda)?;
 
+    Event::Mint { mint, to, amount }.emit();
+    Ok(())
+}
+
+// one user PDA per recipient follows the fixed accounts, in the same order as the recipients
+fn batch_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    recipients: Vec<(Pubkey, u64)>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let minter = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let _system_program = next_account_info(accounts_iter)?;
+    check_role(minter, Role::Minter, &mint, config_pda, program_id)?;
+    check_not_paused(config_pda)?;
+
+    for (to, amount) in recipients.iter() {
+        let to_pda = next_account_info(accounts_iter)?;
+        verify_user_pda(program_id, &mint, to, to_pda)?;
+        if *to_pda.owner == system_program::id() {
+            create_user_pda(program_id, minter, &mint, to, to_pda)?;
+        }
+
+        consume_mint_quota(minter.key, *amount, config_pda)?;
+        increase_supply(*amount, config_pda)?;
+        add(*amount, to_pda)?;
+
+        Event::Mint {
+            mint,
+            to: *to,
+            amount: *amount,
+        }
+        .emit();
+    }
+
     Ok(())
 }
 
 fn transfer(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
+    mint: Pubkey,
     to: Pubkey,
     amount: u64,
+    memo_verified: bool,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.into_iter();
     let from = next_account_info(accounts_iter).unwrap();
+    let config_pda = next_account_info(accounts_iter)?;
     let from_pda = next_account_info(accounts_iter).unwrap();
     let to_pda = next_account_info(accounts_iter).unwrap();
+    let fee_vault_pda = next_account_info(accounts_iter)?;
+    let _system_program = next_account_info(accounts_iter)?;
 
     if !from.is_signer {
         return Err(ProgramError::Custom(
@@ -105,49 +402,741 @@ fn transfer(
         ));
     }
 
-    verify_user_pda(program_id, &from.key, from_pda)?;
-    verify_user_pda(program_id, &to, to_pda)?;
+    check_config_pda(program_id, &mint, config_pda)?;
+    if !memo_verified {
+        storage::check_memo_not_required(config_pda)?;
+    }
+    verify_user_pda(program_id, &mint, &from.key, from_pda)?;
+    verify_user_pda(program_id, &mint, &to, to_pda)?;
     if *from_pda.owner == system_program::id() {
         return Err(ProgramError::InsufficientFunds);
     }
     if *to_pda.owner == system_program::id() {
-        create_user_pda(program_id, from, &to, to_pda)?;
+        create_user_pda(program_id, from, &mint, &to, to_pda)?;
     }
 
-    remove(amount, from_pda)?;
-    add(amount, to_pda)?;
+    let fee = move_tokens(
+        program_id,
+        from,
+        &mint,
+        config_pda,
+        from_pda,
+        to_pda,
+        fee_vault_pda,
+        amount,
+    )?;
+    transfer_hook::execute(
+        config_pda,
+        &mint,
+        from.key,
+        &to,
+        amount,
+        accounts_iter.as_slice(),
+    )?;
 
+    Event::Transfer {
+        mint,
+        from: *from.key,
+        to,
+        amount,
+        fee,
+    }
+    .emit();
     Ok(())
 }
 
+// same accounts as transfer, the decimals guard against amounts scaled for another mint
+fn transfer_checked(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    to: Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    let config_pda = accounts.get(1).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    check_config_pda(program_id, &mint, config_pda)?;
+    check_decimals(decimals, config_pda)?;
+
+    transfer(program_id, accounts, mint, to, amount, false)
+}
+
+// transfer's accounts with the instructions sysvar right after the system program
+fn transfer_with_memo(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    to: Pubkey,
+    amount: u64,
+    memo: String,
+) -> ProgramResult {
+    memo::check_memo_length(&memo)?;
+    let config_pda = accounts.get(1).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    check_config_pda(program_id, &mint, config_pda)?;
+
+    if accounts.len() <= memo::INSTRUCTIONS_SYSVAR_INDEX {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let mut transfer_accounts = accounts.to_vec();
+    let instructions_sysvar = transfer_accounts.remove(memo::INSTRUCTIONS_SYSVAR_INDEX);
+    if Config::try_from_slice(&config_pda.try_borrow_data()?)?.memo_required {
+        memo::verify_memo_instruction(&instructions_sysvar, &memo)?;
+    }
+
+    transfer(program_id, &transfer_accounts, mint, to, amount, true)?;
+
+    msg!("Memo: {}", memo);
+    Event::Memo {
+        mint,
+        from: *accounts[0].key,
+        to,
+        memo,
+    }
+    .emit();
+    Ok(())
+}
+
+// one user PDA per recipient follows the fixed accounts, in the same order as the recipients
+fn batch_transfer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    recipients: Vec<(Pubkey, u64)>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let from = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let from_pda = next_account_info(accounts_iter)?;
+    let fee_vault_pda = next_account_info(accounts_iter)?;
+    let _system_program = next_account_info(accounts_iter)?;
+
+    if !from.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidSigner as u32,
+        ));
+    }
+
+    check_config_pda(program_id, &mint, config_pda)?;
+    storage::check_memo_not_required(config_pda)?;
+    verify_user_pda(program_id, &mint, from.key, from_pda)?;
+    if *from_pda.owner == system_program::id() {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    for (to, amount) in recipients.iter() {
+        let to_pda = next_account_info(accounts_iter)?;
+        verify_user_pda(program_id, &mint, to, to_pda)?;
+        if *to_pda.owner == system_program::id() {
+            create_user_pda(program_id, from, &mint, to, to_pda)?;
+        }
+
+        let fee = move_tokens(
+            program_id,
+            from,
+            &mint,
+            config_pda,
+            from_pda,
+            to_pda,
+            fee_vault_pda,
+            *amount,
+        )?;
+
+        Event::Transfer {
+            mint,
+            from: *from.key,
+            to: *to,
+            amount: *amount,
+            fee,
+        }
+        .emit();
+    }
+
+    // the transfer hook accounts follow the recipient PDAs
+    let hook_accounts = accounts_iter.as_slice();
+    for (to, amount) in recipients.iter() {
+        transfer_hook::execute(config_pda, &mint, from.key, to, *amount, hook_accounts)?;
+    }
+
+    Ok(())
+}
+
+// moves tokens between two user PDAs and keeps back the transfer fee if one is configured,
+// returns the charged fee
+#[allow(clippy::too_many_arguments)]
+fn move_tokens<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    mint: &Pubkey,
+    config_pda: &AccountInfo<'a>,
+    from_pda: &AccountInfo<'a>,
+    to_pda: &AccountInfo<'a>,
+    fee_vault_pda: &AccountInfo<'a>,
+    amount: u64,
+) -> Result<u64, ProgramError> {
+    check_config_pda(program_id, mint, config_pda)?;
+    check_not_paused(config_pda)?;
+    check_transferable(config_pda)?;
+    let fee = match get_transfer_fee(config_pda)? {
+        Some(transfer_fee) => {
+            verify_fee_vault_pda(program_id, mint, &transfer_fee.fee_collector, fee_vault_pda)?;
+            if *fee_vault_pda.owner == system_program::id() {
+                create_fee_vault_pda(
+                    program_id,
+                    payer,
+                    mint,
+                    &transfer_fee.fee_collector,
+                    fee_vault_pda,
+                )?;
+            }
+            transfer_fee.calculate(amount)
+        }
+        None => 0,
+    };
+
+    remove(amount, from_pda)?;
+    add(amount - fee, to_pda)?;
+    if fee > 0 {
+        msg!("Charging a transfer fee of {} tokens", fee);
+        add(fee, fee_vault_pda)?;
+    }
+
+    Ok(fee)
+}
+
 fn burn(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
+    mint: Pubkey,
     from_key: Pubkey,
     amount: u64,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.into_iter();
-    let owner = next_account_info(accounts_iter).unwrap();
+    let burner = next_account_info(accounts_iter).unwrap();
     let config_pda = next_account_info(accounts_iter).unwrap();
-    check_owner(owner, config_pda, program_id)?;
+    check_role(burner, Role::Burner, &mint, config_pda, program_id)?;
+    check_not_paused(config_pda)?;
 
     let from_pda = next_account_info(accounts_iter).unwrap();
 
-    verify_user_pda(program_id, &from_key, from_pda)?;
+    verify_user_pda(program_id, &mint, &from_key, from_pda)?;
 
     if *from_pda.owner != system_program::id() {
         remove(amount, from_pda)?;
+        decrease_supply(amount, config_pda)?;
+
+        Event::Burn {
+            mint,
+            from: from_key,
+            amount,
+        }
+        .emit();
     }
     Ok(())
 }
 
-fn change_owner(program_id: &Pubkey, accounts: &[AccountInfo], new_owner: Pubkey) -> ProgramResult {
-    let accounts_iter = &mut accounts.into_iter();
-    let owner = next_account_info(accounts_iter).unwrap();
-    let config_pda = next_account_info(accounts_iter).unwrap();
+fn burn_own(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let holder = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let holder_pda = next_account_info(accounts_iter)?;
+
+    if !holder.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidSigner as u32,
+        ));
+    }
+
+    check_config_pda(program_id, &mint, config_pda)?;
+    check_not_paused(config_pda)?;
+    verify_user_pda(program_id, &mint, holder.key, holder_pda)?;
+    if *holder_pda.owner == system_program::id() {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    remove(amount, holder_pda)?;
+    decrease_supply(amount, config_pda)?;
+
+    Event::Burn {
+        mint,
+        from: *holder.key,
+        amount,
+    }
+    .emit();
+    Ok(())
+}
+
+fn close_account(program_id: &Pubkey, accounts: &[AccountInfo], mint: Pubkey) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let holder = next_account_info(accounts_iter)?;
+    let holder_pda = next_account_info(accounts_iter)?;
+    let destination = next_account_info(accounts_iter)?;
+
+    if !holder.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidSigner as u32,
+        ));
+    }
+
+    verify_user_pda(program_id, &mint, holder.key, holder_pda)?;
+    if *holder_pda.owner == system_program::id() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    if destination.key == holder_pda.key {
+        return Err(ProgramError::Custom(SimpleTokenErrors::InvalidTo as u32));
+    }
+
+    close_user_pda(holder_pda, destination)?;
+
+    Ok(())
+}
+
+fn set_token_transfer_fee(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    transfer_fee: Option<TransferFee>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let owner = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+
+    check_role(owner, Role::Admin, &mint, config_pda, program_id)?;
+    set_transfer_fee(transfer_fee, config_pda, owner)?;
+
+    Ok(())
+}
+
+fn set_token_transfer_hook(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    transfer_hook_program: Option<Pubkey>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let owner = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+
+    check_role(owner, Role::Admin, &mint, config_pda, program_id)?;
+    set_transfer_hook(transfer_hook_program, config_pda, owner)?;
+
+    Ok(())
+}
+
+fn set_token_memo_required(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    required: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let owner = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+
+    check_role(owner, Role::Admin, &mint, config_pda, program_id)?;
+    set_memo_required(required, config_pda)?;
+
+    Ok(())
+}
+
+fn withdraw_token_fees(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let fee_collector = next_account_info(accounts_iter)?;
+    let fee_vault_pda = next_account_info(accounts_iter)?;
+    let fee_collector_pda = next_account_info(accounts_iter)?;
+
+    if !fee_collector.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidFeeCollector as u32,
+        ));
+    }
+
+    verify_fee_vault_pda(program_id, &mint, fee_collector.key, fee_vault_pda)?;
+    verify_user_pda(program_id, &mint, fee_collector.key, fee_collector_pda)?;
+    if *fee_vault_pda.owner == system_program::id() {
+        msg!("No fees accrued");
+        return Ok(());
+    }
+    if *fee_collector_pda.owner == system_program::id() {
+        create_user_pda(
+            program_id,
+            fee_collector,
+            &mint,
+            fee_collector.key,
+            fee_collector_pda,
+        )?;
+    }
+
+    withdraw_fees(fee_vault_pda, fee_collector_pda)?;
+
+    Ok(())
+}
+
+fn update_token_metadata(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let owner = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+
+    check_role(owner, Role::Admin, &mint, config_pda, program_id)?;
+    update_metadata(name, symbol, uri, config_pda, owner)?;
+
+    Ok(())
+}
+
+fn get_supply(program_id: &Pubkey, accounts: &[AccountInfo], mint: Pubkey) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let config_pda = next_account_info(accounts_iter)?;
+    check_config_pda(program_id, &mint, config_pda)?;
+
+    let config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    let supply = Supply {
+        total_supply: config.total_supply,
+        max_supply: config.max_supply,
+    };
+    set_return_data(&supply.try_to_vec()?);
+
+    Ok(())
+}
+
+fn get_balance_at(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    user: Pubkey,
+    slot: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let user_pda = next_account_info(accounts_iter)?;
+    verify_user_pda(program_id, &mint, &user, user_pda)?;
+
+    let balance = balance_at(slot, user_pda)?;
+    set_return_data(&balance.try_to_vec()?);
+
+    Ok(())
+}
+
+fn propose_owner(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    new_owner: Pubkey,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let owner = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+
+    check_owner(owner, &mint, config_pda, program_id)?;
+    set_pending_owner(Some(new_owner), config_pda, owner)?;
+
+    Ok(())
+}
+
+fn accept_owner(program_id: &Pubkey, accounts: &[AccountInfo], mint: Pubkey) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let new_owner = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+
+    check_config_pda(program_id, &mint, config_pda)?;
+    let previous_owner = accept_ownership(new_owner, config_pda)?;
+
+    Event::ChangeOwner {
+        mint,
+        previous_owner,
+        new_owner: *new_owner.key,
+    }
+    .emit();
+    Ok(())
+}
+
+fn cancel_owner_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let owner = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+
+    check_owner(owner, &mint, config_pda, program_id)?;
+    set_pending_owner(None, config_pda, owner)?;
+
+    Ok(())
+}
+
+fn grant_token_role(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    role: Role,
+    holder: Pubkey,
+    mint_quota: Option<u64>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let authority = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+
+    check_role_manager(authority, role, &mint, config_pda, program_id)?;
+    grant_role(role, holder, mint_quota, config_pda, authority)?;
+
+    Ok(())
+}
+
+fn revoke_token_role(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    role: Role,
+    holder: Pubkey,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let authority = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+
+    check_role_manager(authority, role, &mint, config_pda, program_id)?;
+    revoke_role(role, holder, config_pda, authority)?;
+
+    Ok(())
+}
+
+// admins manage the other roles, only the owner manages admins
+fn check_role_manager(
+    authority: &AccountInfo,
+    role: Role,
+    mint: &Pubkey,
+    config_pda: &AccountInfo,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    match role {
+        Role::Admin => check_owner(authority, mint, config_pda, program_id),
+        _ => check_role(authority, Role::Admin, mint, config_pda, program_id),
+    }
+}
+
+fn set_token_paused(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    paused: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let pauser = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+
+    check_role(pauser, Role::Pauser, &mint, config_pda, program_id)?;
+    set_paused(paused, config_pda)?;
+
+    Ok(())
+}
+
+fn approve(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    delegate: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let owner = next_account_info(accounts_iter)?;
+    let allowance_pda = next_account_info(accounts_iter)?;
+
+    if !owner.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidSigner as u32,
+        ));
+    }
+
+    verify_allowance_pda(program_id, &mint, owner.key, &delegate, allowance_pda)?;
+    if *allowance_pda.owner == system_program::id() {
+        create_allowance_pda(
+            program_id,
+            owner,
+            &mint,
+            owner.key,
+            &delegate,
+            allowance_pda,
+        )?;
+    }
+
+    set_allowance(amount, allowance_pda)?;
+
+    Ok(())
+}
+
+fn transfer_from(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    from: Pubkey,
+    to: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let delegate = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let allowance_pda = next_account_info(accounts_iter)?;
+    let from_pda = next_account_info(accounts_iter)?;
+    let to_pda = next_account_info(accounts_iter)?;
+    let fee_vault_pda = next_account_info(accounts_iter)?;
+    let _system_program = next_account_info(accounts_iter)?;
+
+    if !delegate.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidSigner as u32,
+        ));
+    }
+
+    check_config_pda(program_id, &mint, config_pda)?;
+    storage::check_memo_not_required(config_pda)?;
+    verify_allowance_pda(program_id, &mint, &from, delegate.key, allowance_pda)?;
+    verify_user_pda(program_id, &mint, &from, from_pda)?;
+    verify_user_pda(program_id, &mint, &to, to_pda)?;
+    if *allowance_pda.owner == system_program::id() {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InsufficientAllowance as u32,
+        ));
+    }
+    if *from_pda.owner == system_program::id() {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    if *to_pda.owner == system_program::id() {
+        create_user_pda(program_id, delegate, &mint, &to, to_pda)?;
+    }
+
+    spend_allowance(amount, allowance_pda)?;
+    let fee = move_tokens(
+        program_id,
+        delegate,
+        &mint,
+        config_pda,
+        from_pda,
+        to_pda,
+        fee_vault_pda,
+        amount,
+    )?;
+    transfer_hook::execute(
+        config_pda,
+        &mint,
+        &from,
+        &to,
+        amount,
+        accounts_iter.as_slice(),
+    )?;
+
+    Event::Transfer {
+        mint,
+        from,
+        to,
+        amount,
+        fee,
+    }
+    .emit();
+    Ok(())
+}
+
+fn revoke(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    delegate: Pubkey,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let owner = next_account_info(accounts_iter)?;
+    let allowance_pda = next_account_info(accounts_iter)?;
+
+    if !owner.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidSigner as u32,
+        ));
+    }
+
+    verify_allowance_pda(program_id, &mint, owner.key, &delegate, allowance_pda)?;
+    if *allowance_pda.owner != system_program::id() {
+        set_allowance(0, allowance_pda)?;
+    }
+
+    Ok(())
+}
+
+// works on frozen accounts and while paused, no fee is taken and the transfer hook isn't asked
+fn force_transfer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    from: Pubkey,
+    to: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let clawback_authority = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let from_pda = next_account_info(accounts_iter)?;
+    let to_pda = next_account_info(accounts_iter)?;
+
+    check_clawback_authority(clawback_authority, &mint, config_pda, program_id)?;
+    verify_user_pda(program_id, &mint, &from, from_pda)?;
+    verify_user_pda(program_id, &mint, &to, to_pda)?;
+    if *from_pda.owner == system_program::id() {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    if *to_pda.owner == system_program::id() {
+        create_user_pda(program_id, clawback_authority, &mint, &to, to_pda)?;
+    }
+
+    msg!(
+        "Force transferring {} tokens from {} to {}",
+        amount,
+        from,
+        to
+    );
+    force_remove(amount, from_pda)?;
+    add(amount, to_pda)?;
+
+    Event::ForceTransfer {
+        mint,
+        authority: *clawback_authority.key,
+        from,
+        to,
+        amount,
+    }
+    .emit();
+    Ok(())
+}
+
+fn set_account_frozen(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    user: Pubkey,
+    frozen: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let freeze_authority = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    check_freeze_authority(freeze_authority, &mint, config_pda, program_id)?;
+
+    let user_pda = next_account_info(accounts_iter)?;
+
+    verify_user_pda(program_id, &mint, &user, user_pda)?;
+    if *user_pda.owner == system_program::id() {
+        create_user_pda(program_id, freeze_authority, &mint, &user, user_pda)?;
+    }
 
-    check_owner(owner, config_pda, program_id)?;
-    update_owner(new_owner, config_pda)?;
+    set_frozen(frozen, user_pda)?;
 
     Ok(())
 }
@@ -0,0 +1,63 @@
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey,
+    pubkey::Pubkey,
+    sysvar::{
+        self,
+        instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+
+use crate::errors::SimpleTokenErrors;
+
+// the SPL Memo program and its legacy v1 deployment
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+pub const LEGACY_MEMO_PROGRAM_ID: Pubkey = pubkey!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+
+pub const MAX_MEMO_LENGTH: usize = 256;
+
+// TransferWithMemo takes the instructions sysvar after transfer's fixed accounts
+pub const INSTRUCTIONS_SYSVAR_INDEX: usize = 6;
+
+fn missing_memo() -> ProgramError {
+    ProgramError::Custom(SimpleTokenErrors::MissingMemo as u32)
+}
+
+pub fn check_memo_length(memo: &str) -> ProgramResult {
+    if memo.len() > MAX_MEMO_LENGTH {
+        msg!("Memo longer than {} bytes", MAX_MEMO_LENGTH);
+        return Err(ProgramError::Custom(SimpleTokenErrors::MemoTooLong as u32));
+    }
+    Ok(())
+}
+
+// the instruction right before the transfer has to log the same memo through SPL Memo
+pub fn verify_memo_instruction(instructions_sysvar: &AccountInfo, memo: &str) -> ProgramResult {
+    if !sysvar::instructions::check_id(instructions_sysvar.key) {
+        msg!("Invalid instructions sysvar");
+        return Err(missing_memo());
+    }
+
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    if current_index == 0 {
+        msg!("Missing memo instruction");
+        return Err(missing_memo());
+    }
+    let memo_instruction =
+        load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    if memo_instruction.program_id != MEMO_PROGRAM_ID
+        && memo_instruction.program_id != LEGACY_MEMO_PROGRAM_ID
+    {
+        msg!("Missing memo instruction");
+        return Err(missing_memo());
+    }
+    if memo_instruction.data != memo.as_bytes() {
+        msg!("Memo instruction doesn't match the transfer memo");
+        return Err(missing_memo());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    ed25519_program,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::{
+        self,
+        instructions::{load_current_index_checked, load_instruction_at_checked},
+        Sysvar,
+    },
+};
+
+use crate::{errors::SimpleTokenErrors, storage};
+
+// the bytes the owner signs off-chain, program_id and mint keep a permit from being replayed elsewhere
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct PermitMessage {
+    pub program_id: Pubkey,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+    pub expiry: i64,
+}
+
+// layout of the ed25519 precompile data: count, padding and one offsets entry of seven u16
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+fn invalid_signature() -> ProgramError {
+    ProgramError::Custom(SimpleTokenErrors::InvalidPermitSignature as u32)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, ProgramError> {
+    let bytes = data.get(offset..offset + 2).ok_or_else(invalid_signature)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_slice(data: &[u8], offset: u16, len: usize) -> Result<&[u8], ProgramError> {
+    let start = offset as usize;
+    data.get(start..start + len).ok_or_else(invalid_signature)
+}
+
+// the instruction right before the permit has to be an ed25519 verification of message by signer
+fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> ProgramResult {
+    if !sysvar::instructions::check_id(instructions_sysvar.key) {
+        msg!("Invalid instructions sysvar");
+        return Err(invalid_signature());
+    }
+
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    if current_index == 0 {
+        msg!("Missing ed25519 instruction");
+        return Err(invalid_signature());
+    }
+    let ed25519_instruction =
+        load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    if ed25519_instruction.program_id != ed25519_program::id() {
+        msg!("Missing ed25519 instruction");
+        return Err(invalid_signature());
+    }
+
+    let data = &ed25519_instruction.data;
+    if data.len() < SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN || data[0] != 1 {
+        msg!("Expected exactly one ed25519 signature");
+        return Err(invalid_signature());
+    }
+
+    let offsets = SIGNATURE_OFFSETS_START;
+    let signature_instruction_index = read_u16(data, offsets + 2)?;
+    let public_key_offset = read_u16(data, offsets + 4)?;
+    let public_key_instruction_index = read_u16(data, offsets + 6)?;
+    let message_data_offset = read_u16(data, offsets + 8)?;
+    let message_data_size = read_u16(data, offsets + 10)?;
+    let message_instruction_index = read_u16(data, offsets + 12)?;
+
+    // the precompile only checked the bytes it was pointed at, so they have to live in its own data
+    if signature_instruction_index != CURRENT_INSTRUCTION
+        || public_key_instruction_index != CURRENT_INSTRUCTION
+        || message_instruction_index != CURRENT_INSTRUCTION
+    {
+        msg!("Signature data has to be part of the ed25519 instruction");
+        return Err(invalid_signature());
+    }
+
+    let public_key = read_slice(data, public_key_offset, 32)?;
+    let signed_message = read_slice(data, message_data_offset, message_data_size as usize)?;
+    if public_key != signer.as_ref() || signed_message != message {
+        msg!("Permit not signed by the owner");
+        return Err(invalid_signature());
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn permit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    owner: Pubkey,
+    delegate: Pubkey,
+    amount: u64,
+    nonce: u64,
+    expiry: i64,
+) -> ProgramResult {
+    msg!(
+        "Permit {} to spend {} tokens of {}",
+        delegate,
+        amount,
+        owner
+    );
+    let accounts_iter = &mut accounts.iter();
+    let relayer = next_account_info(accounts_iter)?;
+    let permit_nonce_pda = next_account_info(accounts_iter)?;
+    let allowance_pda = next_account_info(accounts_iter)?;
+    let instructions_sysvar = next_account_info(accounts_iter)?;
+
+    if !relayer.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidSigner as u32,
+        ));
+    }
+
+    if Clock::get()?.unix_timestamp > expiry {
+        msg!("Permit expired at {}", expiry);
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::PermitExpired as u32,
+        ));
+    }
+
+    let message = PermitMessage {
+        program_id: *program_id,
+        mint,
+        owner,
+        delegate,
+        amount,
+        nonce,
+        expiry,
+    };
+    verify_ed25519_signature(instructions_sysvar, &owner, &message.try_to_vec()?)?;
+
+    // the relayer pays for the nonce PDA of an owner's first permit
+    storage::verify_permit_nonce_pda(program_id, &mint, &owner, permit_nonce_pda)?;
+    if *permit_nonce_pda.owner == system_program::id() {
+        storage::create_permit_nonce_pda(program_id, relayer, &mint, &owner, permit_nonce_pda)?;
+    }
+    storage::use_permit_nonce(nonce, permit_nonce_pda)?;
+
+    storage::verify_allowance_pda(program_id, &mint, &owner, &delegate, allowance_pda)?;
+    if *allowance_pda.owner == system_program::id() {
+        storage::create_allowance_pda(
+            program_id,
+            relayer,
+            &mint,
+            &owner,
+            &delegate,
+            allowance_pda,
+        )?;
+    }
+    storage::set_allowance(amount, allowance_pda)
+}
@@ -0,0 +1,360 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use crate::{errors::SimpleTokenErrors, events::Event, storage};
+
+// reward_per_token is scaled up so pools with a large total stake still accrue something
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+// staked tokens and funded rewards both stay in the supply, the pool just holds them
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct StakePool {
+    pub reward_rate: u64,
+    pub reward_reserve: u64,
+    pub total_staked: u64,
+    pub reward_per_token: u128,
+    pub last_update_ts: i64,
+}
+
+impl StakePool {
+    pub const LEN: usize = 8 + 8 + 8 + 16 + 8;
+
+    // reward_rate tokens per second are shared by all stakers until the reserve runs dry
+    pub fn accrue(&mut self, now: i64) {
+        let elapsed = now.saturating_sub(self.last_update_ts).max(0) as u128;
+        self.last_update_ts = now;
+        if self.total_staked == 0 {
+            return;
+        }
+
+        let rewards = (elapsed * self.reward_rate as u128).min(self.reward_reserve as u128);
+        let increment = rewards * REWARD_PRECISION / self.total_staked as u128;
+        // only what the rounded down increment pays out leaves the reserve, the rest stays for later
+        let distributed = increment * self.total_staked as u128 / REWARD_PRECISION;
+        self.reward_reserve -= distributed as u64;
+        self.reward_per_token += increment;
+    }
+
+    fn get(
+        program_id: &Pubkey,
+        mint: &Pubkey,
+        account: &AccountInfo,
+    ) -> Result<StakePool, ProgramError> {
+        verify_stake_pool_pda(program_id, mint, account)?;
+        storage::load_state(account, "No stake pool")
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct StakeAccount {
+    pub staker: Pubkey,
+    pub amount: u64,
+    pub reward_per_token_paid: u128,
+    pub pending_rewards: u64,
+}
+
+impl StakeAccount {
+    pub const LEN: usize = 32 + 8 + 16 + 8;
+
+    // moves the rewards accrued since the last update into pending_rewards
+    pub fn update(&mut self, pool: &StakePool) {
+        let earned = self.amount as u128 * (pool.reward_per_token - self.reward_per_token_paid)
+            / REWARD_PRECISION;
+        self.pending_rewards += earned as u64;
+        self.reward_per_token_paid = pool.reward_per_token;
+    }
+
+    fn get_or_create<'a>(
+        program_id: &Pubkey,
+        mint: &Pubkey,
+        staker: &AccountInfo<'a>,
+        account: &AccountInfo<'a>,
+        pool: &StakePool,
+    ) -> Result<StakeAccount, ProgramError> {
+        verify_stake_pda(program_id, mint, staker.key, account)?;
+        if *account.owner != system_program::id() {
+            return storage::load_state(account, "Nothing staked");
+        }
+
+        storage::create_pda(
+            program_id,
+            staker,
+            &[b"stake", mint.as_ref(), staker.key.as_ref()],
+            account,
+            StakeAccount::LEN,
+        )?;
+        // a new stake only earns from now on
+        Ok(StakeAccount {
+            staker: *staker.key,
+            amount: 0,
+            reward_per_token_paid: pool.reward_per_token,
+            pending_rewards: 0,
+        })
+    }
+}
+
+pub fn verify_stake_pool_pda(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    stake_pool_pda: &AccountInfo,
+) -> ProgramResult {
+    storage::verify_pda(program_id, &[b"stake_pool", mint.as_ref()], stake_pool_pda)
+}
+
+pub fn verify_stake_pda(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    staker: &Pubkey,
+    stake_pda: &AccountInfo,
+) -> ProgramResult {
+    storage::verify_pda(
+        program_id,
+        &[b"stake", mint.as_ref(), staker.as_ref()],
+        stake_pda,
+    )
+}
+
+// creates the pool on first use, rewards accrued so far are settled at the previous rate
+pub fn fund_rewards(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    amount: u64,
+    reward_rate: u64,
+) -> ProgramResult {
+    msg!(
+        "Funding {} staking rewards at {} per second",
+        amount,
+        reward_rate
+    );
+    let accounts_iter = &mut accounts.iter();
+    let owner = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let owner_pda = next_account_info(accounts_iter)?;
+    let stake_pool_pda = next_account_info(accounts_iter)?;
+
+    storage::check_owner(owner, &mint, config_pda, program_id)?;
+    storage::verify_user_pda(program_id, &mint, owner.key, owner_pda)?;
+    verify_stake_pool_pda(program_id, &mint, stake_pool_pda)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut pool = if *stake_pool_pda.owner == system_program::id() {
+        storage::create_pda(
+            program_id,
+            owner,
+            &[b"stake_pool", mint.as_ref()],
+            stake_pool_pda,
+            StakePool::LEN,
+        )?;
+        StakePool {
+            reward_rate: 0,
+            reward_reserve: 0,
+            total_staked: 0,
+            reward_per_token: 0,
+            last_update_ts: now,
+        }
+    } else {
+        StakePool::get(program_id, &mint, stake_pool_pda)?
+    };
+    pool.accrue(now);
+
+    if *owner_pda.owner == system_program::id() {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    storage::remove(amount, owner_pda)?;
+    pool.reward_reserve = pool
+        .reward_reserve
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    pool.reward_rate = reward_rate;
+    storage::save_state(&pool, stake_pool_pda)?;
+
+    Event::Transfer {
+        mint,
+        from: *owner.key,
+        to: *stake_pool_pda.key,
+        amount,
+        fee: 0,
+    }
+    .emit();
+    Ok(())
+}
+
+pub fn stake(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    msg!("Staking {} tokens", amount);
+    let accounts_iter = &mut accounts.iter();
+    let staker = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let staker_pda = next_account_info(accounts_iter)?;
+    let stake_pool_pda = next_account_info(accounts_iter)?;
+    let stake_pda = next_account_info(accounts_iter)?;
+
+    if !staker.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidSigner as u32,
+        ));
+    }
+
+    storage::check_config_pda(program_id, &mint, config_pda)?;
+    storage::check_not_paused(config_pda)?;
+    storage::verify_user_pda(program_id, &mint, staker.key, staker_pda)?;
+    if *staker_pda.owner == system_program::id() {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    let mut pool = StakePool::get(program_id, &mint, stake_pool_pda)?;
+    pool.accrue(Clock::get()?.unix_timestamp);
+    let mut stake = StakeAccount::get_or_create(program_id, &mint, staker, stake_pda, &pool)?;
+    stake.update(&pool);
+
+    storage::remove(amount, staker_pda)?;
+    stake.amount += amount;
+    pool.total_staked += amount;
+
+    storage::save_state(&stake, stake_pda)?;
+    storage::save_state(&pool, stake_pool_pda)?;
+
+    Event::Transfer {
+        mint,
+        from: *staker.key,
+        to: *stake_pool_pda.key,
+        amount,
+        fee: 0,
+    }
+    .emit();
+    Ok(())
+}
+
+pub fn unstake(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    msg!("Unstaking {} tokens", amount);
+    let accounts_iter = &mut accounts.iter();
+    let staker = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let staker_pda = next_account_info(accounts_iter)?;
+    let stake_pool_pda = next_account_info(accounts_iter)?;
+    let stake_pda = next_account_info(accounts_iter)?;
+
+    let (mut pool, mut stake) = settle(
+        program_id,
+        &mint,
+        staker,
+        config_pda,
+        staker_pda,
+        stake_pool_pda,
+        stake_pda,
+    )?;
+    if stake.amount < amount {
+        msg!("Only {} tokens staked", stake.amount);
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InsufficientStake as u32,
+        ));
+    }
+
+    stake.amount -= amount;
+    pool.total_staked -= amount;
+    storage::add(amount, staker_pda)?;
+
+    storage::save_state(&stake, stake_pda)?;
+    storage::save_state(&pool, stake_pool_pda)?;
+
+    Event::Transfer {
+        mint,
+        from: *stake_pool_pda.key,
+        to: *staker.key,
+        amount,
+        fee: 0,
+    }
+    .emit();
+    Ok(())
+}
+
+pub fn claim_rewards(program_id: &Pubkey, accounts: &[AccountInfo], mint: Pubkey) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let staker = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let staker_pda = next_account_info(accounts_iter)?;
+    let stake_pool_pda = next_account_info(accounts_iter)?;
+    let stake_pda = next_account_info(accounts_iter)?;
+
+    let (pool, mut stake) = settle(
+        program_id,
+        &mint,
+        staker,
+        config_pda,
+        staker_pda,
+        stake_pool_pda,
+        stake_pda,
+    )?;
+    let rewards = stake.pending_rewards;
+    msg!("Claiming {} staking rewards", rewards);
+
+    stake.pending_rewards = 0;
+    storage::add(rewards, staker_pda)?;
+
+    storage::save_state(&stake, stake_pda)?;
+    storage::save_state(&pool, stake_pool_pda)?;
+
+    // rewards come out of the reserve the pool holds, so they are a transfer and not a mint
+    Event::Transfer {
+        mint,
+        from: *stake_pool_pda.key,
+        to: *staker.key,
+        amount: rewards,
+        fee: 0,
+    }
+    .emit();
+    Ok(())
+}
+
+// shared by unstake and claim_rewards: checks the accounts and brings pool and stake up to date
+fn settle<'a>(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    staker: &AccountInfo<'a>,
+    config_pda: &AccountInfo<'a>,
+    staker_pda: &AccountInfo<'a>,
+    stake_pool_pda: &AccountInfo<'a>,
+    stake_pda: &AccountInfo<'a>,
+) -> Result<(StakePool, StakeAccount), ProgramError> {
+    if !staker.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidSigner as u32,
+        ));
+    }
+
+    storage::check_config_pda(program_id, mint, config_pda)?;
+    storage::check_not_paused(config_pda)?;
+    storage::verify_user_pda(program_id, mint, staker.key, staker_pda)?;
+    verify_stake_pda(program_id, mint, staker.key, stake_pda)?;
+    let mut stake: StakeAccount = storage::load_state(stake_pda, "Nothing staked")?;
+
+    let mut pool = StakePool::get(program_id, mint, stake_pool_pda)?;
+    pool.accrue(Clock::get()?.unix_timestamp);
+    stake.update(&pool);
+
+    // the user PDA may have been closed while everything was staked
+    if *staker_pda.owner == system_program::id() {
+        storage::create_user_pda(program_id, staker, mint, staker.key, staker_pda)?;
+    }
+
+    Ok((pool, stake))
+}
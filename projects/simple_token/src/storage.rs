@@ -2,28 +2,186 @@ use std::mem;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, msg, program::invoke_signed,
-    program_error::ProgramError, pubkey::Pubkey, rent::Rent, system_instruction, system_program,
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction, system_program,
     sysvar::Sysvar,
 };
 
 use crate::errors::SimpleTokenErrors;
 
+#[derive(BorshDeserialize, BorshSerialize, Debug, Default, Clone, Copy, PartialEq)]
+pub struct Checkpoint {
+    pub slot: u64,
+    pub balance: u64,
+}
+
+pub const MAX_CHECKPOINTS: usize = 16;
+
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct Account {
     pub balance: u64,
+    pub frozen: bool,
+    // ring of the balances after each change, next_checkpoint is the slot to overwrite next
+    pub checkpoints: [Checkpoint; MAX_CHECKPOINTS],
+    pub checkpoint_count: u8,
+    pub next_checkpoint: u8,
+    // set by the first overwrite, a full ring alone doesn't mean anything was lost yet
+    pub checkpoints_evicted: bool,
+}
+
+impl Account {
+    pub const LEN: usize = 8 + 1 + MAX_CHECKPOINTS * (8 + 8) + 1 + 1 + 1;
+
+    // several changes within the same slot only keep the last balance, and a change which leaves
+    // the balance as it was isn't recorded so zero amount transfers can't evict the history
+    fn record_checkpoint(&mut self, slot: u64) {
+        if self.checkpoint_count > 0 {
+            let latest = (self.next_checkpoint as usize + MAX_CHECKPOINTS - 1) % MAX_CHECKPOINTS;
+            if self.checkpoints[latest].balance == self.balance {
+                return;
+            }
+            if self.checkpoints[latest].slot == slot {
+                self.checkpoints[latest].balance = self.balance;
+                return;
+            }
+        } else if self.balance == 0 {
+            return;
+        }
+
+        if self.checkpoint_count as usize == MAX_CHECKPOINTS {
+            self.checkpoints_evicted = true;
+        }
+        self.checkpoints[self.next_checkpoint as usize] = Checkpoint {
+            slot,
+            balance: self.balance,
+        };
+        self.next_checkpoint = ((self.next_checkpoint as usize + 1) % MAX_CHECKPOINTS) as u8;
+        if (self.checkpoint_count as usize) < MAX_CHECKPOINTS {
+            self.checkpoint_count += 1;
+        }
+    }
+
+    // None once the requested slot is older than the oldest checkpoint still in the ring
+    pub fn balance_at(&self, slot: u64) -> Option<u64> {
+        for offset in 1..=self.checkpoint_count as usize {
+            let index =
+                (self.next_checkpoint as usize + MAX_CHECKPOINTS - offset) % MAX_CHECKPOINTS;
+            if self.checkpoints[index].slot <= slot {
+                return Some(self.checkpoints[index].balance);
+            }
+        }
+
+        if !self.checkpoints_evicted {
+            // the account was empty before its first checkpoint
+            return Some(0);
+        }
+        None
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Allowance {
+    pub amount: u64,
+}
+
+// the nonce the next permit of a holder has to be signed with. It has its own PDA which is never
+// closed, a nonce kept in the closable token account would start over at 0 and allow replays
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PermitNonce {
+    pub nonce: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq)]
+pub struct TransferFee {
+    pub basis_points: u16,
+    pub maximum_fee: u64,
+    pub fee_collector: Pubkey,
+}
+
+impl TransferFee {
+    pub fn calculate(&self, amount: u64) -> u64 {
+        let fee = amount as u128 * self.basis_points as u128 / MAX_BASIS_POINTS as u128;
+        // fits into u64 as basis_points never exceeds MAX_BASIS_POINTS
+        (fee as u64).min(self.maximum_fee)
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Copy, PartialEq)]
+pub enum Role {
+    Minter,
+    Burner,
+    Admin,
+    Pauser,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq)]
+pub struct RoleGrant {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub mint_quota: Option<u64>,
+    pub minted: u64,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Debug)]
 pub struct Config {
     pub owner: Pubkey,
+    pub pending_owner: Option<Pubkey>,
+    pub roles: Vec<RoleGrant>,
+    pub paused: bool,
     pub decimals: u8,
+    pub freeze_authority: Option<Pubkey>,
+    pub clawback_authority: Option<Pubkey>,
+    // only set by Initialize, nothing changes it afterwards
+    pub non_transferable: bool,
+    pub total_supply: u64,
+    pub max_supply: Option<u64>,
+    pub transfer_fee: Option<TransferFee>,
+    pub transfer_hook_program: Option<Pubkey>,
+    pub memo_required: bool,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
 }
 
+impl Config {
+    // the owner implicitly holds every role
+    pub fn has_role(&self, role: Role, holder: &Pubkey) -> bool {
+        self.owner == *holder
+            || self
+                .roles
+                .iter()
+                .any(|grant| grant.role == role && grant.holder == *holder)
+    }
+}
+
+pub const MAX_NAME_LENGTH: usize = 32;
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+pub const MAX_URI_LENGTH: usize = 200;
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
 pub fn remove<'a>(amount: u64, from_pda: &AccountInfo<'a>) -> ProgramResult {
+    withdraw(amount, from_pda, true)
+}
+
+// the clawback authority can take tokens out of frozen accounts as well
+pub fn force_remove<'a>(amount: u64, from_pda: &AccountInfo<'a>) -> ProgramResult {
+    withdraw(amount, from_pda, false)
+}
+
+fn withdraw<'a>(amount: u64, from_pda: &AccountInfo<'a>, respect_frozen: bool) -> ProgramResult {
     msg!("Removing {} tokens", amount);
     let mut pda_data = from_pda.try_borrow_mut_data()?;
     let mut account = Account::try_from_slice(&pda_data)?;
+    if respect_frozen {
+        check_not_frozen(&account)?;
+    }
     if account.balance < amount {
         msg!("Insufficient funds");
         return Err(ProgramError::InsufficientFunds);
@@ -37,6 +195,7 @@ pub fn remove<'a>(amount: u64, from_pda: &AccountInfo<'a>) -> ProgramResult {
             return Err(ProgramError::InsufficientFunds);
         }
     }
+    account.record_checkpoint(Clock::get()?.slot);
     write_to_pda(pda_data.as_mut(), &account.try_to_vec()?);
     Ok(())
 }
@@ -45,6 +204,7 @@ pub fn add<'a>(amount: u64, to_pda: &AccountInfo<'a>) -> ProgramResult {
     msg!("Adding {} tokens", amount);
     let mut pda_data = to_pda.try_borrow_mut_data()?;
     let mut account = Account::try_from_slice(&pda_data)?;
+    check_not_frozen(&account)?;
 
     let new_balance = account.balance.checked_add(amount);
     match new_balance {
@@ -54,50 +214,465 @@ pub fn add<'a>(amount: u64, to_pda: &AccountInfo<'a>) -> ProgramResult {
             return Err(ProgramError::ArithmeticOverflow);
         }
     }
+    account.record_checkpoint(Clock::get()?.slot);
     write_to_pda(pda_data.as_mut(), &account.try_to_vec()?);
     Ok(())
 }
 
-pub fn update_owner<'a>(new_owner: Pubkey, config_pda: &AccountInfo<'a>) -> ProgramResult {
+pub fn set_frozen<'a>(frozen: bool, user_pda: &AccountInfo<'a>) -> ProgramResult {
+    msg!("Setting frozen to {}", frozen);
+    let mut pda_data = user_pda.try_borrow_mut_data()?;
+    let mut account = Account::try_from_slice(&pda_data)?;
+    account.frozen = frozen;
+    write_to_pda(pda_data.as_mut(), &account.try_to_vec()?);
+    Ok(())
+}
+
+pub fn use_permit_nonce<'a>(nonce: u64, permit_nonce_pda: &AccountInfo<'a>) -> ProgramResult {
+    let mut pda_data = permit_nonce_pda.try_borrow_mut_data()?;
+    let mut permit_nonce = PermitNonce::try_from_slice(&pda_data)?;
+    if permit_nonce.nonce != nonce {
+        msg!("Expected permit nonce {}", permit_nonce.nonce);
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidPermitNonce as u32,
+        ));
+    }
+
+    permit_nonce.nonce += 1;
+    write_to_pda(pda_data.as_mut(), &permit_nonce.try_to_vec()?);
+    Ok(())
+}
+
+// the balance is only final once the slot has passed. Closing an account deletes its checkpoints,
+// so closed accounts report 0 for every slot
+pub fn balance_at<'a>(slot: u64, user_pda: &AccountInfo<'a>) -> Result<u64, ProgramError> {
+    let current_slot = Clock::get()?.slot;
+    if slot >= current_slot {
+        msg!(
+            "Slot {} is not in the past, current slot is {}",
+            slot,
+            current_slot
+        );
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::CheckpointUnavailable as u32,
+        ));
+    }
+
+    // accounts which never held tokens have no PDA yet
+    if *user_pda.owner == system_program::id() {
+        return Ok(0);
+    }
+
+    let account = Account::try_from_slice(&user_pda.try_borrow_data()?)?;
+    match account.balance_at(slot) {
+        Some(balance) => Ok(balance),
+        None => {
+            msg!("No checkpoint at slot {}", slot);
+            Err(ProgramError::Custom(
+                SimpleTokenErrors::CheckpointUnavailable as u32,
+            ))
+        }
+    }
+}
+
+// the balance history goes with the account, BalanceAt can't tell a closed account from one which
+// never held tokens
+pub fn close_user_pda<'a>(
+    user_pda: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+) -> ProgramResult {
+    let account = Account::try_from_slice(&user_pda.try_borrow_data()?)?;
+    check_not_frozen(&account)?;
+    if account.balance != 0 {
+        msg!("Account still holds {} tokens", account.balance);
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::NonZeroBalance as u32,
+        ));
+    }
+
+    close_pda(user_pda, destination)
+}
+
+fn check_not_frozen(account: &Account) -> ProgramResult {
+    if account.frozen {
+        msg!("Account is frozen");
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::AccountFrozen as u32,
+        ));
+    }
+    Ok(())
+}
+
+pub fn set_allowance<'a>(amount: u64, allowance_pda: &AccountInfo<'a>) -> ProgramResult {
+    msg!("Setting allowance to {} tokens", amount);
+    let mut pda_data = allowance_pda.try_borrow_mut_data()?;
+    let allowance = Allowance { amount };
+    write_to_pda(pda_data.as_mut(), &allowance.try_to_vec()?);
+    Ok(())
+}
+
+pub fn spend_allowance<'a>(amount: u64, allowance_pda: &AccountInfo<'a>) -> ProgramResult {
+    msg!("Spending {} tokens of allowance", amount);
+    let mut pda_data = allowance_pda.try_borrow_mut_data()?;
+    let mut allowance = Allowance::try_from_slice(&pda_data)?;
+    match allowance.amount.checked_sub(amount) {
+        Some(new_amount) => allowance.amount = new_amount,
+        None => {
+            msg!("Insufficient allowance");
+            return Err(ProgramError::Custom(
+                SimpleTokenErrors::InsufficientAllowance as u32,
+            ));
+        }
+    }
+    write_to_pda(pda_data.as_mut(), &allowance.try_to_vec()?);
+    Ok(())
+}
+
+pub fn set_pending_owner<'a>(
+    pending_owner: Option<Pubkey>,
+    config_pda: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+) -> ProgramResult {
+    msg!("Setting pending owner to {:?}", pending_owner);
+    let mut config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    config.pending_owner = pending_owner;
+
+    let config_data = config.try_to_vec()?;
+    resize_pda(config_pda, config_data.len(), payer)?;
+    let mut pda_data = config_pda.try_borrow_mut_data()?;
+    write_to_pda(pda_data.as_mut(), &config_data);
+    Ok(())
+}
+
+// returns the previous owner
+pub fn accept_ownership<'a>(
+    new_owner: &AccountInfo<'a>,
+    config_pda: &AccountInfo<'a>,
+) -> Result<Pubkey, ProgramError> {
+    let mut config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    if !new_owner.is_signer || config.pending_owner != Some(*new_owner.key) {
+        msg!("Invalid pending owner");
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidPendingOwner as u32,
+        ));
+    }
+
     msg!("Changing owner");
+    let previous_owner = config.owner;
+    config.owner = *new_owner.key;
+    config.pending_owner = None;
+
+    let config_data = config.try_to_vec()?;
+    resize_pda(config_pda, config_data.len(), new_owner)?;
+    let mut pda_data = config_pda.try_borrow_mut_data()?;
+    write_to_pda(pda_data.as_mut(), &config_data);
+    Ok(previous_owner)
+}
+
+pub fn grant_role<'a>(
+    role: Role,
+    holder: Pubkey,
+    mint_quota: Option<u64>,
+    config_pda: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+) -> ProgramResult {
+    msg!("Granting {:?} to {}", role, holder);
+    let mut config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    match config
+        .roles
+        .iter_mut()
+        .find(|grant| grant.role == role && grant.holder == holder)
+    {
+        Some(grant) => grant.mint_quota = mint_quota,
+        None => config.roles.push(RoleGrant {
+            role,
+            holder,
+            mint_quota,
+            minted: 0,
+        }),
+    }
+
+    let config_data = config.try_to_vec()?;
+    resize_pda(config_pda, config_data.len(), payer)?;
+    let mut pda_data = config_pda.try_borrow_mut_data()?;
+    write_to_pda(pda_data.as_mut(), &config_data);
+    Ok(())
+}
+
+pub fn revoke_role<'a>(
+    role: Role,
+    holder: Pubkey,
+    config_pda: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+) -> ProgramResult {
+    msg!("Revoking {:?} from {}", role, holder);
+    let mut config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    config
+        .roles
+        .retain(|grant| grant.role != role || grant.holder != holder);
+
+    let config_data = config.try_to_vec()?;
+    resize_pda(config_pda, config_data.len(), payer)?;
+    let mut pda_data = config_pda.try_borrow_mut_data()?;
+    write_to_pda(pda_data.as_mut(), &config_data);
+    Ok(())
+}
+
+// counts the amount against the minter's quota, the owner has no quota
+pub fn consume_mint_quota<'a>(
+    minter: &Pubkey,
+    amount: u64,
+    config_pda: &AccountInfo<'a>,
+) -> ProgramResult {
+    let mut pda_data = config_pda.try_borrow_mut_data()?;
+    let mut config = Config::try_from_slice(&pda_data)?;
+    if config.owner == *minter {
+        return Ok(());
+    }
+
+    let grant = config
+        .roles
+        .iter_mut()
+        .find(|grant| grant.role == Role::Minter && grant.holder == *minter)
+        .ok_or(ProgramError::Custom(SimpleTokenErrors::InvalidOwner as u32))?;
+    let minted = grant
+        .minted
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if let Some(mint_quota) = grant.mint_quota {
+        if minted > mint_quota {
+            msg!("Mint quota exceeded");
+            return Err(ProgramError::Custom(
+                SimpleTokenErrors::MintQuotaExceeded as u32,
+            ));
+        }
+    }
+    grant.minted = minted;
+    write_to_pda(pda_data.as_mut(), &config.try_to_vec()?);
+    Ok(())
+}
+
+pub fn check_decimals<'a>(decimals: u8, config_pda: &AccountInfo<'a>) -> ProgramResult {
+    let config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    if config.decimals != decimals {
+        msg!("Expected {} decimals, got {}", config.decimals, decimals);
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidDecimals as u32,
+        ));
+    }
+    Ok(())
+}
+
+pub fn set_paused<'a>(paused: bool, config_pda: &AccountInfo<'a>) -> ProgramResult {
+    msg!("Setting paused to {}", paused);
+    let mut pda_data = config_pda.try_borrow_mut_data()?;
+    let mut config = Config::try_from_slice(&pda_data)?;
+    config.paused = paused;
+    write_to_pda(pda_data.as_mut(), &config.try_to_vec()?);
+    Ok(())
+}
+
+pub fn set_memo_required<'a>(required: bool, config_pda: &AccountInfo<'a>) -> ProgramResult {
+    msg!("Setting memo required to {}", required);
+    let mut pda_data = config_pda.try_borrow_mut_data()?;
+    let mut config = Config::try_from_slice(&pda_data)?;
+    config.memo_required = required;
+    write_to_pda(pda_data.as_mut(), &config.try_to_vec()?);
+    Ok(())
+}
+
+pub fn check_not_paused<'a>(config_pda: &AccountInfo<'a>) -> ProgramResult {
+    let config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    if config.paused {
+        msg!("Token is paused");
+        return Err(ProgramError::Custom(SimpleTokenErrors::Paused as u32));
+    }
+    Ok(())
+}
+
+// mint, burn and the clawback authority still work on non-transferable tokens
+pub fn check_transferable<'a>(config_pda: &AccountInfo<'a>) -> ProgramResult {
+    let config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    if config.non_transferable {
+        msg!("Token is non-transferable");
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::NonTransferable as u32,
+        ));
+    }
+    Ok(())
+}
+
+// once a memo is required TransferWithMemo is the only way to move tokens between holders
+pub fn check_memo_not_required<'a>(config_pda: &AccountInfo<'a>) -> ProgramResult {
+    let config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    if config.memo_required {
+        msg!("Transfers of this token require a memo");
+        return Err(ProgramError::Custom(SimpleTokenErrors::MissingMemo as u32));
+    }
+    Ok(())
+}
+
+pub fn increase_supply<'a>(amount: u64, config_pda: &AccountInfo<'a>) -> ProgramResult {
+    let mut pda_data = config_pda.try_borrow_mut_data()?;
+    let mut config = Config::try_from_slice(&pda_data)?;
+    let new_supply = match config.total_supply.checked_add(amount) {
+        Some(new_supply) => new_supply,
+        None => {
+            msg!("Overflow");
+            return Err(ProgramError::ArithmeticOverflow);
+        }
+    };
+    if let Some(max_supply) = config.max_supply {
+        if new_supply > max_supply {
+            msg!("Max supply exceeded");
+            return Err(ProgramError::Custom(
+                SimpleTokenErrors::MaxSupplyExceeded as u32,
+            ));
+        }
+    }
+    config.total_supply = new_supply;
+    write_to_pda(pda_data.as_mut(), &config.try_to_vec()?);
+    Ok(())
+}
+
+pub fn decrease_supply<'a>(amount: u64, config_pda: &AccountInfo<'a>) -> ProgramResult {
     let mut pda_data = config_pda.try_borrow_mut_data()?;
     let mut config = Config::try_from_slice(&pda_data)?;
-    config.owner = new_owner;
+    match config.total_supply.checked_sub(amount) {
+        Some(new_supply) => config.total_supply = new_supply,
+        None => {
+            msg!("Underflow");
+            return Err(ProgramError::InsufficientFunds);
+        }
+    }
     write_to_pda(pda_data.as_mut(), &config.try_to_vec()?);
     Ok(())
 }
 
+pub fn update_metadata<'a>(
+    name: String,
+    symbol: String,
+    uri: String,
+    config_pda: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+) -> ProgramResult {
+    msg!("Updating metadata");
+    check_metadata(&name, &symbol, &uri)?;
+    let mut config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    config.name = name;
+    config.symbol = symbol;
+    config.uri = uri;
+
+    let config_data = config.try_to_vec()?;
+    resize_pda(config_pda, config_data.len(), payer)?;
+    let mut pda_data = config_pda.try_borrow_mut_data()?;
+    write_to_pda(pda_data.as_mut(), &config_data);
+    Ok(())
+}
+
+pub fn set_transfer_fee<'a>(
+    transfer_fee: Option<TransferFee>,
+    config_pda: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+) -> ProgramResult {
+    msg!("Setting transfer fee to {:?}", transfer_fee);
+    if let Some(transfer_fee) = &transfer_fee {
+        if transfer_fee.basis_points > MAX_BASIS_POINTS {
+            msg!("Transfer fee above 100%");
+            return Err(ProgramError::Custom(
+                SimpleTokenErrors::InvalidTransferFee as u32,
+            ));
+        }
+    }
+    let mut config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    config.transfer_fee = transfer_fee;
+
+    let config_data = config.try_to_vec()?;
+    resize_pda(config_pda, config_data.len(), payer)?;
+    let mut pda_data = config_pda.try_borrow_mut_data()?;
+    write_to_pda(pda_data.as_mut(), &config_data);
+    Ok(())
+}
+
+pub fn set_transfer_hook<'a>(
+    transfer_hook_program: Option<Pubkey>,
+    config_pda: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+) -> ProgramResult {
+    msg!("Setting transfer hook to {:?}", transfer_hook_program);
+    let mut config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    config.transfer_hook_program = transfer_hook_program;
+
+    let config_data = config.try_to_vec()?;
+    resize_pda(config_pda, config_data.len(), payer)?;
+    let mut pda_data = config_pda.try_borrow_mut_data()?;
+    write_to_pda(pda_data.as_mut(), &config_data);
+    Ok(())
+}
+
+pub fn get_transfer_fee<'a>(
+    config_pda: &AccountInfo<'a>,
+) -> Result<Option<TransferFee>, ProgramError> {
+    let config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    Ok(config.transfer_fee)
+}
+
+pub fn withdraw_fees<'a>(
+    fee_vault_pda: &AccountInfo<'a>,
+    to_pda: &AccountInfo<'a>,
+) -> ProgramResult {
+    let fees = Account::try_from_slice(&fee_vault_pda.try_borrow_data()?)?.balance;
+    msg!("Withdrawing {} tokens of fees", fees);
+    remove(fees, fee_vault_pda)?;
+    add(fees, to_pda)
+}
+
+fn check_metadata(name: &str, symbol: &str, uri: &str) -> ProgramResult {
+    if name.len() > MAX_NAME_LENGTH
+        || symbol.len() > MAX_SYMBOL_LENGTH
+        || uri.len() > MAX_URI_LENGTH
+    {
+        msg!("Metadata too long");
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::MetadataTooLong as u32,
+        ));
+    }
+    Ok(())
+}
+
 pub fn initialize_config<'a>(
     program_id: &Pubkey,
     owner_info: &AccountInfo<'a>,
-    owner: &Pubkey,
-    decimals: u8,
+    mint: &Pubkey,
+    config: &Config,
     config_pda: &AccountInfo<'a>,
 ) -> ProgramResult {
     msg!("Initializing config");
+    check_metadata(&config.name, &config.symbol, &config.uri)?;
+    let config_data = config.try_to_vec()?;
     create_pda(
         program_id,
         owner_info,
-        &[b"config"],
+        &[b"config", mint.as_ref()],
         config_pda,
-        mem::size_of::<Config>(),
+        config_data.len(),
     )?;
-    config_pda.realloc(33, true)?;
     let mut pda_data = config_pda.try_borrow_mut_data()?;
-    let config = Config {
-        owner: *owner,
-        decimals: decimals,
-    };
-    write_to_pda(pda_data.as_mut(), &config.try_to_vec()?);
+    write_to_pda(pda_data.as_mut(), &config_data);
     Ok(())
 }
 
-pub fn check_config_pda<'a>(program_id: &Pubkey, config_pda: &AccountInfo<'a>) -> ProgramResult {
-    verify_pda(program_id, &[b"config"], config_pda)
+pub fn check_config_pda<'a>(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    config_pda: &AccountInfo<'a>,
+) -> ProgramResult {
+    verify_pda(program_id, &[b"config", mint.as_ref()], config_pda)
 }
 
 pub fn check_owner(
     owner: &AccountInfo,
+    mint: &Pubkey,
     config_pda: &AccountInfo,
     program_id: &Pubkey,
 ) -> ProgramResult {
@@ -106,7 +681,7 @@ pub fn check_owner(
         return Err(ProgramError::Custom(SimpleTokenErrors::InvalidOwner as u32));
     }
 
-    check_config_pda(program_id, config_pda)?;
+    check_config_pda(program_id, mint, config_pda)?;
 
     let pda_data = config_pda.try_borrow_data()?;
     let account = Config::try_from_slice(&pda_data)?;
@@ -118,12 +693,136 @@ pub fn check_owner(
     Ok(())
 }
 
+pub fn check_role(
+    authority: &AccountInfo,
+    role: Role,
+    mint: &Pubkey,
+    config_pda: &AccountInfo,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    if !authority.is_signer {
+        msg!("Missing {:?} role", role);
+        return Err(ProgramError::Custom(SimpleTokenErrors::InvalidOwner as u32));
+    }
+
+    check_config_pda(program_id, mint, config_pda)?;
+
+    let pda_data = config_pda.try_borrow_data()?;
+    let config = Config::try_from_slice(&pda_data)?;
+    if !config.has_role(role, authority.key) {
+        msg!("Missing {:?} role", role);
+        return Err(ProgramError::Custom(SimpleTokenErrors::InvalidOwner as u32));
+    }
+    msg!("{:?} role verified", role);
+    Ok(())
+}
+
+pub fn check_freeze_authority(
+    freeze_authority: &AccountInfo,
+    mint: &Pubkey,
+    config_pda: &AccountInfo,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    if !freeze_authority.is_signer {
+        msg!("Invalid freeze authority");
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidFreezeAuthority as u32,
+        ));
+    }
+
+    check_config_pda(program_id, mint, config_pda)?;
+
+    let pda_data = config_pda.try_borrow_data()?;
+    let config = Config::try_from_slice(&pda_data)?;
+    if config.freeze_authority != Some(*freeze_authority.key) {
+        msg!("Invalid freeze authority");
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidFreezeAuthority as u32,
+        ));
+    }
+    msg!("Freeze authority verified");
+    Ok(())
+}
+
+pub fn check_clawback_authority(
+    clawback_authority: &AccountInfo,
+    mint: &Pubkey,
+    config_pda: &AccountInfo,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    if !clawback_authority.is_signer {
+        msg!("Invalid clawback authority");
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidClawbackAuthority as u32,
+        ));
+    }
+
+    check_config_pda(program_id, mint, config_pda)?;
+
+    let pda_data = config_pda.try_borrow_data()?;
+    let config = Config::try_from_slice(&pda_data)?;
+    if config.clawback_authority != Some(*clawback_authority.key) {
+        msg!("Invalid clawback authority");
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidClawbackAuthority as u32,
+        ));
+    }
+    msg!("Clawback authority verified");
+    Ok(())
+}
+
 pub fn verify_user_pda(
     program_id: &Pubkey,
+    mint: &Pubkey,
     user: &Pubkey,
     user_pda: &AccountInfo,
 ) -> ProgramResult {
-    return verify_pda(program_id, &[user.as_ref()], user_pda);
+    return verify_pda(program_id, &[mint.as_ref(), user.as_ref()], user_pda);
+}
+
+pub fn verify_fee_vault_pda(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    fee_collector: &Pubkey,
+    fee_vault_pda: &AccountInfo,
+) -> ProgramResult {
+    verify_pda(
+        program_id,
+        &[b"fees", mint.as_ref(), fee_collector.as_ref()],
+        fee_vault_pda,
+    )
+}
+
+pub fn verify_allowance_pda(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+    allowance_pda: &AccountInfo,
+) -> ProgramResult {
+    verify_pda(
+        program_id,
+        &[
+            b"allowance",
+            mint.as_ref(),
+            owner.as_ref(),
+            delegate.as_ref(),
+        ],
+        allowance_pda,
+    )
+}
+
+pub fn verify_permit_nonce_pda(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    permit_nonce_pda: &AccountInfo,
+) -> ProgramResult {
+    verify_pda(
+        program_id,
+        &[b"permit_nonce", mint.as_ref(), owner.as_ref()],
+        permit_nonce_pda,
+    )
 }
 
 pub fn verify_pda(program_id: &Pubkey, seeds: &[&[u8]], pda: &AccountInfo) -> ProgramResult {
@@ -144,24 +843,91 @@ pub fn verify_pda(program_id: &Pubkey, seeds: &[&[u8]], pda: &AccountInfo) -> Pr
 pub fn create_user_pda<'a>(
     program_id: &Pubkey,
     payer: &AccountInfo<'a>,
+    mint: &Pubkey,
     user_key: &Pubkey,
     user_pda: &AccountInfo<'a>,
 ) -> ProgramResult {
     return create_pda(
         program_id,
         payer,
-        &[user_key.as_ref()],
+        &[mint.as_ref(), user_key.as_ref()],
         user_pda,
-        mem::size_of::<Account>(),
+        Account::LEN,
     );
 }
 
+pub fn create_fee_vault_pda<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    mint: &Pubkey,
+    fee_collector: &Pubkey,
+    fee_vault_pda: &AccountInfo<'a>,
+) -> ProgramResult {
+    create_pda(
+        program_id,
+        payer,
+        &[b"fees", mint.as_ref(), fee_collector.as_ref()],
+        fee_vault_pda,
+        Account::LEN,
+    )
+}
+
+pub fn create_allowance_pda<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+    allowance_pda: &AccountInfo<'a>,
+) -> ProgramResult {
+    create_pda(
+        program_id,
+        payer,
+        &[
+            b"allowance",
+            mint.as_ref(),
+            owner.as_ref(),
+            delegate.as_ref(),
+        ],
+        allowance_pda,
+        mem::size_of::<Allowance>(),
+    )
+}
+
+pub fn create_permit_nonce_pda<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    permit_nonce_pda: &AccountInfo<'a>,
+) -> ProgramResult {
+    create_pda(
+        program_id,
+        payer,
+        &[b"permit_nonce", mint.as_ref(), owner.as_ref()],
+        permit_nonce_pda,
+        mem::size_of::<PermitNonce>(),
+    )
+}
+
 pub fn create_pda<'a>(
     program_id: &Pubkey,
     payer: &AccountInfo<'a>,
     seeds: &[&[u8]],
     pda: &AccountInfo<'a>,
     account_size: usize,
+) -> ProgramResult {
+    create_pda_owned_by(program_id, payer, seeds, pda, account_size, program_id)
+}
+
+// a PDA of program_id handed to another program, e.g. the wrapped SPL mint
+pub fn create_pda_owned_by<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    seeds: &[&[u8]],
+    pda: &AccountInfo<'a>,
+    account_size: usize,
+    owner: &Pubkey,
 ) -> ProgramResult {
     let (pda_key, pda_bump) = Pubkey::find_program_address(seeds, program_id);
     if pda.owner != &solana_program::system_program::id() {
@@ -186,7 +952,12 @@ pub fn create_pda<'a>(
             .unwrap();
         }
         invoke_signed(
-            &system_instruction::assign(&pda.key, program_id),
+            &system_instruction::allocate(pda.key, account_size.try_into().unwrap()),
+            std::slice::from_ref(pda),
+            &[seeds_vec.as_slice()],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(&pda.key, owner),
             &[pda.clone()],
             &[seeds_vec.as_slice()],
         )
@@ -200,7 +971,7 @@ pub fn create_pda<'a>(
             &pda_key,
             rent_lamports,
             account_size.try_into().unwrap(),
-            program_id,
+            owner,
         ),
         &[payer.clone(), pda.clone()],
         &[seeds_vec.as_slice()],
@@ -210,6 +981,65 @@ pub fn create_pda<'a>(
     return Ok(());
 }
 
-fn write_to_pda(pda_data: &mut [u8], data: &[u8]) {
+// hands the account back to the system program so the same address can be created again
+pub fn close_pda<'a>(pda: &AccountInfo<'a>, destination: &AccountInfo<'a>) -> ProgramResult {
+    let lamports = pda.lamports();
+    **pda.try_borrow_mut_lamports()? -= lamports;
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    pda.try_borrow_mut_data()?.fill(0);
+    pda.realloc(0, false)?;
+    pda.assign(&system_program::id());
+    msg!("PDA ({}) closed, {} lamports refunded", pda.key, lamports);
+    Ok(())
+}
+
+pub fn resize_pda<'a>(
+    pda: &AccountInfo<'a>,
+    new_size: usize,
+    payer: &AccountInfo<'a>,
+) -> ProgramResult {
+    let rent = Rent::get()?;
+    let rent_lamports = rent.minimum_balance(new_size);
+
+    if rent_lamports > pda.lamports() {
+        let missing_rent = rent_lamports - pda.lamports();
+        invoke(
+            &system_instruction::transfer(payer.key, pda.key, missing_rent),
+            &[payer.clone(), pda.clone()],
+        )?;
+    } else if rent_lamports < pda.lamports() {
+        let excess_rent = pda.lamports() - rent_lamports;
+        **pda.try_borrow_mut_lamports()? -= excess_rent;
+        **payer.try_borrow_mut_lamports()? += excess_rent;
+    }
+    pda.realloc(new_size, false)?;
+    msg!("PDA ({}) resized with size: {}", pda.key, new_size);
+    Ok(())
+}
+
+// the vesting, staking and airdrop PDAs each hold a single borsh encoded state
+pub fn save_state<T: BorshSerialize>(state: &T, pda: &AccountInfo) -> ProgramResult {
+    let mut pda_data = pda.try_borrow_mut_data()?;
+    write_to_pda(pda_data.as_mut(), &state.try_to_vec()?);
+    Ok(())
+}
+
+pub fn load_state<T: BorshDeserialize>(
+    pda: &AccountInfo,
+    missing: &str,
+) -> Result<T, ProgramError> {
+    if *pda.owner == system_program::id() {
+        msg!("{}", missing);
+        return Err(ProgramError::UninitializedAccount);
+    }
+    let pda_data = pda.try_borrow_data()?;
+    Ok(T::try_from_slice(&pda_data)?)
+}
+
+pub fn write_to_pda(pda_data: &mut [u8], data: &[u8]) {
     pda_data[0..data.len()].copy_from_slice(data);
 }
@@ -0,0 +1,73 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{errors::SimpleTokenErrors, storage::Config};
+
+// instruction data the transfer hook program receives for every transfer
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq)]
+pub struct TransferHookExecute {
+    pub mint: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+}
+
+// hook_accounts starts with the hook program, everything after it is passed through to the hook
+pub fn execute<'a>(
+    config_pda: &AccountInfo<'a>,
+    mint: &Pubkey,
+    from: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+    hook_accounts: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let config = Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    let transfer_hook_program = match config.transfer_hook_program {
+        Some(transfer_hook_program) => transfer_hook_program,
+        None => return Ok(()),
+    };
+
+    let (hook_program, extra_accounts) = match hook_accounts.split_first() {
+        Some((hook_program, extra_accounts)) if *hook_program.key == transfer_hook_program => {
+            (hook_program, extra_accounts)
+        }
+        _ => {
+            msg!("Missing transfer hook program {}", transfer_hook_program);
+            return Err(ProgramError::Custom(
+                SimpleTokenErrors::InvalidTransferHook as u32,
+            ));
+        }
+    };
+
+    // signatures of this transaction are never forwarded, the hook only gets to read and veto
+    let account_metas = extra_accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: *account.key,
+            is_signer: false,
+            is_writable: account.is_writable,
+        })
+        .collect();
+    let data = TransferHookExecute {
+        mint: *mint,
+        from: *from,
+        to: *to,
+        amount,
+    };
+
+    msg!("Calling transfer hook {}", transfer_hook_program);
+    let mut account_infos = extra_accounts.to_vec();
+    account_infos.push(hook_program.clone());
+    invoke(
+        &Instruction::new_with_bytes(transfer_hook_program, &data.try_to_vec()?, account_metas),
+        &account_infos,
+    )
+}
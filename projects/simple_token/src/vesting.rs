@@ -0,0 +1,192 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    errors::SimpleTokenErrors,
+    events::Event,
+    storage::{self, Role},
+};
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct VestingSchedule {
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub claimed: u64,
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+}
+
+impl VestingSchedule {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 8;
+
+    fn new(
+        beneficiary: Pubkey,
+        total_amount: u64,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+    ) -> Result<Self, ProgramError> {
+        if start_ts > cliff_ts || cliff_ts > end_ts || start_ts >= end_ts {
+            msg!("Invalid vesting schedule");
+            return Err(ProgramError::Custom(
+                SimpleTokenErrors::InvalidVestingSchedule as u32,
+            ));
+        }
+        Ok(VestingSchedule {
+            beneficiary,
+            total_amount,
+            claimed: 0,
+            start_ts,
+            cliff_ts,
+            end_ts,
+        })
+    }
+
+    // linear unlock between start and end, nothing before the cliff
+    pub fn unlocked(&self, now: i64) -> u64 {
+        if now < self.cliff_ts {
+            return 0;
+        }
+        if now >= self.end_ts {
+            return self.total_amount;
+        }
+        let elapsed = (now - self.start_ts) as u128;
+        let duration = (self.end_ts - self.start_ts) as u128;
+        (self.total_amount as u128 * elapsed / duration) as u64
+    }
+
+    fn get(
+        program_id: &Pubkey,
+        mint: &Pubkey,
+        beneficiary: &Pubkey,
+        account: &AccountInfo,
+    ) -> Result<VestingSchedule, ProgramError> {
+        verify_vesting_pda(program_id, mint, beneficiary, account)?;
+        storage::load_state(account, "No vesting schedule")
+    }
+
+    fn create<'a>(
+        &self,
+        program_id: &Pubkey,
+        mint: &Pubkey,
+        payer: &AccountInfo<'a>,
+        account: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        storage::create_pda(
+            program_id,
+            payer,
+            &[b"vesting", mint.as_ref(), self.beneficiary.as_ref()],
+            account,
+            VestingSchedule::LEN,
+        )?;
+        storage::save_state(self, account)
+    }
+}
+
+pub fn verify_vesting_pda(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    beneficiary: &Pubkey,
+    vesting_pda: &AccountInfo,
+) -> ProgramResult {
+    storage::verify_pda(
+        program_id,
+        &[b"vesting", mint.as_ref(), beneficiary.as_ref()],
+        vesting_pda,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn mint_vested(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    to: Pubkey,
+    amount: u64,
+    start_ts: i64,
+    cliff_ts: i64,
+    end_ts: i64,
+) -> ProgramResult {
+    msg!("Minting {} vested tokens to {}", amount, to);
+    let accounts_iter = &mut accounts.iter();
+    let minter = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let vesting_pda = next_account_info(accounts_iter)?;
+
+    storage::check_role(minter, Role::Minter, &mint, config_pda, program_id)?;
+    storage::check_not_paused(config_pda)?;
+    verify_vesting_pda(program_id, &mint, &to, vesting_pda)?;
+
+    let schedule = VestingSchedule::new(to, amount, start_ts, cliff_ts, end_ts)?;
+    schedule.create(program_id, &mint, minter, vesting_pda)?;
+
+    // vested tokens count towards the supply as soon as they are minted
+    storage::consume_mint_quota(minter.key, amount, config_pda)?;
+    storage::increase_supply(amount, config_pda)?;
+
+    Ok(())
+}
+
+pub fn claim(program_id: &Pubkey, accounts: &[AccountInfo], mint: Pubkey) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let beneficiary = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let vesting_pda = next_account_info(accounts_iter)?;
+    let beneficiary_pda = next_account_info(accounts_iter)?;
+
+    if !beneficiary.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidSigner as u32,
+        ));
+    }
+
+    storage::check_config_pda(program_id, &mint, config_pda)?;
+    storage::check_not_paused(config_pda)?;
+    let mut schedule = VestingSchedule::get(program_id, &mint, beneficiary.key, vesting_pda)?;
+    storage::verify_user_pda(program_id, &mint, beneficiary.key, beneficiary_pda)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let claimable = schedule.unlocked(now) - schedule.claimed;
+    msg!("Claiming {} vested tokens", claimable);
+    if claimable == 0 {
+        return Ok(());
+    }
+
+    if *beneficiary_pda.owner == system_program::id() {
+        storage::create_user_pda(
+            program_id,
+            beneficiary,
+            &mint,
+            beneficiary.key,
+            beneficiary_pda,
+        )?;
+    }
+
+    schedule.claimed += claimable;
+    storage::add(claimable, beneficiary_pda)?;
+
+    // MintVested only locks the tokens away, they reach a balance here
+    Event::Mint {
+        mint,
+        to: *beneficiary.key,
+        amount: claimable,
+    }
+    .emit();
+
+    // a fully claimed schedule is closed, which frees the PDA for another grant. The rent goes to
+    // the beneficiary on purpose, the schedule doesn't keep track of which minter paid for it
+    if schedule.claimed == schedule.total_amount {
+        return storage::close_pda(vesting_pda, beneficiary);
+    }
+    storage::save_state(&schedule, vesting_pda)
+}
@@ -0,0 +1,221 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::{errors::SimpleTokenErrors, events::Event, storage, transfer_hook};
+
+// the SPL mint and its mint authority are both derived from the simple_token mint
+pub fn find_wrapped_mint(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"wrapped_mint", mint.as_ref()], program_id)
+}
+
+pub fn find_wrap_authority(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"wrap_authority", mint.as_ref()], program_id)
+}
+
+// the wrapped mint is owned by the token program, so verify_pda's owner check doesn't apply
+fn verify_wrapped_mint(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    wrapped_mint: &AccountInfo,
+) -> ProgramResult {
+    if find_wrapped_mint(program_id, mint).0 != *wrapped_mint.key {
+        msg!("Invalid wrapped mint");
+        return Err(ProgramError::Custom(SimpleTokenErrors::InvalidPda as u32));
+    }
+    Ok(())
+}
+
+fn verify_token_program(token_program: &AccountInfo) -> ProgramResult {
+    if *token_program.key != spl_token::id() {
+        msg!("Invalid token program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+pub fn initialize_wrapped_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+) -> ProgramResult {
+    msg!("Initializing wrapped mint");
+    let accounts_iter = &mut accounts.iter();
+    let payer = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let wrapped_mint = next_account_info(accounts_iter)?;
+    let wrap_authority = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let _system_program = next_account_info(accounts_iter)?;
+
+    storage::check_config_pda(program_id, &mint, config_pda)?;
+    verify_wrapped_mint(program_id, &mint, wrapped_mint)?;
+    verify_token_program(token_program)?;
+
+    let (wrap_authority_key, _) = find_wrap_authority(program_id, &mint);
+    if wrap_authority_key != *wrap_authority.key {
+        msg!("Invalid wrap authority");
+        return Err(ProgramError::Custom(SimpleTokenErrors::InvalidPda as u32));
+    }
+
+    let config = storage::Config::try_from_slice(&config_pda.try_borrow_data()?)?;
+    // lamports sent to the address beforehand must not block the creation
+    storage::create_pda_owned_by(
+        program_id,
+        payer,
+        &[b"wrapped_mint", mint.as_ref()],
+        wrapped_mint,
+        spl_token::state::Mint::LEN,
+        &spl_token::id(),
+    )?;
+
+    invoke(
+        &spl_token::instruction::initialize_mint2(
+            &spl_token::id(),
+            wrapped_mint.key,
+            wrap_authority.key,
+            None,
+            config.decimals,
+        )?,
+        std::slice::from_ref(wrapped_mint),
+    )
+}
+
+pub fn wrap(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    msg!("Wrapping {} tokens", amount);
+    let accounts_iter = &mut accounts.iter();
+    let holder = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let holder_pda = next_account_info(accounts_iter)?;
+    let wrapped_mint = next_account_info(accounts_iter)?;
+    let wrap_authority = next_account_info(accounts_iter)?;
+    let token_account = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+
+    if !holder.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidSigner as u32,
+        ));
+    }
+
+    storage::check_config_pda(program_id, &mint, config_pda)?;
+    storage::check_not_paused(config_pda)?;
+    // wrapped tokens could be moved freely through the token program
+    storage::check_transferable(config_pda)?;
+    storage::verify_user_pda(program_id, &mint, holder.key, holder_pda)?;
+    verify_wrapped_mint(program_id, &mint, wrapped_mint)?;
+    verify_token_program(token_program)?;
+
+    // wrapped tokens are still backed by simple_token, so they stay in the supply and the cap
+    storage::remove(amount, holder_pda)?;
+
+    let (_, wrap_authority_bump) = find_wrap_authority(program_id, &mint);
+    invoke_signed(
+        &spl_token::instruction::mint_to(
+            &spl_token::id(),
+            wrapped_mint.key,
+            token_account.key,
+            wrap_authority.key,
+            &[],
+            amount,
+        )?,
+        &[
+            wrapped_mint.clone(),
+            token_account.clone(),
+            wrap_authority.clone(),
+        ],
+        &[&[b"wrap_authority", mint.as_ref(), &[wrap_authority_bump]]],
+    )?;
+
+    Event::Transfer {
+        mint,
+        from: *holder.key,
+        to: *wrap_authority.key,
+        amount,
+        fee: 0,
+    }
+    .emit();
+    Ok(())
+}
+
+pub fn unwrap(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    msg!("Unwrapping {} tokens", amount);
+    let accounts_iter = &mut accounts.iter();
+    let holder = next_account_info(accounts_iter)?;
+    let config_pda = next_account_info(accounts_iter)?;
+    let holder_pda = next_account_info(accounts_iter)?;
+    let wrapped_mint = next_account_info(accounts_iter)?;
+    let token_account = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let _system_program = next_account_info(accounts_iter)?;
+
+    if !holder.is_signer {
+        return Err(ProgramError::Custom(
+            SimpleTokenErrors::InvalidSigner as u32,
+        ));
+    }
+
+    storage::check_config_pda(program_id, &mint, config_pda)?;
+    storage::check_not_paused(config_pda)?;
+    storage::verify_user_pda(program_id, &mint, holder.key, holder_pda)?;
+    verify_wrapped_mint(program_id, &mint, wrapped_mint)?;
+    verify_token_program(token_program)?;
+
+    // the token program makes sure the holder owns the token account
+    invoke(
+        &spl_token::instruction::burn(
+            &spl_token::id(),
+            token_account.key,
+            wrapped_mint.key,
+            holder.key,
+            &[],
+            amount,
+        )?,
+        &[token_account.clone(), wrapped_mint.clone(), holder.clone()],
+    )?;
+
+    if *holder_pda.owner == system_program::id() {
+        storage::create_user_pda(program_id, holder, &mint, holder.key, holder_pda)?;
+    }
+    storage::add(amount, holder_pda)?;
+
+    // wrapped tokens can change hands through the token program, so the transfer hook gets to veto
+    // whoever unwraps them, the transfer hook accounts follow the system program
+    let (wrap_authority, _) = find_wrap_authority(program_id, &mint);
+    transfer_hook::execute(
+        config_pda,
+        &mint,
+        &wrap_authority,
+        holder.key,
+        amount,
+        accounts_iter.as_slice(),
+    )?;
+
+    Event::Transfer {
+        mint,
+        from: wrap_authority,
+        to: *holder.key,
+        amount,
+        fee: 0,
+    }
+    .emit();
+    Ok(())
+}
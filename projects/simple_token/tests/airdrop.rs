@@ -0,0 +1,234 @@
+use simple_token::{
+    airdrop::{leaf, verify_proof},
+    client::merkle_tree,
+    errors::SimpleTokenErrors,
+};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, claim_airdrop, clawback_airdrop, create_airdrop, get_account, get_config,
+    initialize, prepare, sol, transfer_sol, warp_to_timestamp,
+};
+
+struct Airdrop {
+    recipients: Vec<(Keypair, u64)>,
+    merkle_root: [u8; 32],
+    proofs: Vec<Vec<[u8; 32]>>,
+}
+
+// three funded recipients of 1, 2 and 3 tokens, claimable until 2_000
+async fn create_test_airdrop(
+    context: &mut ProgramTestContext,
+    owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+) -> Airdrop {
+    let mut recipients = Vec::new();
+    for amount in [sol(1.0), sol(2.0), sol(3.0)] {
+        let recipient = Keypair::new();
+        transfer_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &recipient.pubkey(),
+            sol(1.0),
+        )
+        .await
+        .unwrap();
+        recipients.push((recipient, amount));
+    }
+    let leaves: Vec<[u8; 32]> = recipients
+        .iter()
+        .enumerate()
+        .map(|(index, (recipient, amount))| leaf(index as u64, &recipient.pubkey(), *amount))
+        .collect();
+    let (merkle_root, proofs) = merkle_tree(&leaves);
+
+    warp_to_timestamp(context, 1_000).await;
+    create_airdrop(
+        owner,
+        program_id,
+        mint,
+        &mut context.banks_client,
+        merkle_root,
+        sol(6.0),
+        2_000,
+    )
+    .await
+    .unwrap();
+
+    Airdrop {
+        recipients,
+        merkle_root,
+        proofs,
+    }
+}
+
+#[tokio::test]
+async fn test_claim_airdrop() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    let airdrop = create_test_airdrop(&mut context, &owner, &program_id, &mint).await;
+
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config.total_supply, sol(6.0));
+
+    for (index, (recipient, amount)) in airdrop.recipients.iter().enumerate() {
+        claim_airdrop(
+            recipient,
+            &program_id,
+            &mint,
+            &mut context.banks_client,
+            airdrop.merkle_root,
+            index as u64,
+            *amount,
+            airdrop.proofs[index].clone(),
+        )
+        .await
+        .unwrap();
+
+        let account = get_account(
+            &mut context.banks_client,
+            &program_id,
+            &mint,
+            &recipient.pubkey(),
+        )
+        .await;
+        assert_eq!(account.balance, *amount);
+    }
+}
+
+#[tokio::test]
+async fn test_fail_claim_airdrop_twice() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    let airdrop = create_test_airdrop(&mut context, &owner, &program_id, &mint).await;
+    let (recipient, amount) = &airdrop.recipients[1];
+
+    claim_airdrop(
+        recipient,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        airdrop.merkle_root,
+        1,
+        *amount,
+        airdrop.proofs[1].clone(),
+    )
+    .await
+    .unwrap();
+    warp_to_timestamp(&mut context, 1_100).await;
+    let result = claim_airdrop(
+        recipient,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        airdrop.merkle_root,
+        1,
+        *amount,
+        airdrop.proofs[1].clone(),
+    )
+    .await;
+
+    assert_custom_error(result, SimpleTokenErrors::AlreadyClaimed);
+}
+
+#[tokio::test]
+async fn test_fail_claim_airdrop_invalid_proof() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    let airdrop = create_test_airdrop(&mut context, &owner, &program_id, &mint).await;
+    let (recipient, _) = &airdrop.recipients[0];
+
+    let result = claim_airdrop(
+        recipient,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        airdrop.merkle_root,
+        0,
+        sol(3.0),
+        airdrop.proofs[0].clone(),
+    )
+    .await;
+
+    assert_custom_error(result, SimpleTokenErrors::InvalidMerkleProof);
+}
+
+#[tokio::test]
+async fn test_clawback_airdrop() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    let airdrop = create_test_airdrop(&mut context, &owner, &program_id, &mint).await;
+    let (recipient, amount) = &airdrop.recipients[0];
+
+    claim_airdrop(
+        recipient,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        airdrop.merkle_root,
+        0,
+        *amount,
+        airdrop.proofs[0].clone(),
+    )
+    .await
+    .unwrap();
+    let early_clawback = clawback_airdrop(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        airdrop.merkle_root,
+    )
+    .await;
+    assert_custom_error(early_clawback, SimpleTokenErrors::AirdropNotExpired);
+
+    warp_to_timestamp(&mut context, 2_001).await;
+    let (late_recipient, late_amount) = &airdrop.recipients[2];
+    let late_claim = claim_airdrop(
+        late_recipient,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        airdrop.merkle_root,
+        2,
+        *late_amount,
+        airdrop.proofs[2].clone(),
+    )
+    .await;
+    assert_custom_error(late_claim, SimpleTokenErrors::AirdropExpired);
+
+    clawback_airdrop(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        airdrop.merkle_root,
+    )
+    .await
+    .unwrap();
+    let owner_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &owner.pubkey(),
+    )
+    .await;
+    assert_eq!(owner_account.balance, sol(5.0));
+}
+
+#[test]
+fn test_merkle_tree_proofs() {
+    let leaves: Vec<[u8; 32]> = (0..5)
+        .map(|index| leaf(index, &Pubkey::new_unique(), index * 10))
+        .collect();
+    let (root, proofs) = merkle_tree(&leaves);
+
+    for (leaf, proof) in leaves.iter().zip(proofs.iter()) {
+        assert!(verify_proof(proof, &root, *leaf));
+    }
+    assert!(!verify_proof(&proofs[0], &root, leaves[1]));
+}
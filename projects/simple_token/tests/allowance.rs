@@ -0,0 +1,280 @@
+use simple_token::errors::SimpleTokenErrors;
+use solana_program::instruction::InstructionError;
+use solana_program_test::{tokio, BanksClientError};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+mod helpers;
+use crate::helpers::{
+    approve, get_account, get_allowance, initialize, mint_to, prepare, revoke, sol,
+    transfer_from_token, transfer_sol,
+};
+
+#[tokio::test]
+async fn test_approve() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let delegate = Keypair::new();
+
+    approve(
+        &owner,
+        &delegate,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(3.0),
+    )
+    .await
+    .unwrap();
+
+    let allowance = get_allowance(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &owner.pubkey(),
+        &delegate.pubkey(),
+    )
+    .await;
+    assert_eq!(allowance.amount, sol(3.0));
+}
+
+#[tokio::test]
+async fn test_transfer_from() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let delegate = Keypair::new();
+    let to = Keypair::new();
+
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &delegate.pubkey(),
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    approve(
+        &from,
+        &delegate,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(5.0),
+    )
+    .await
+    .unwrap();
+
+    transfer_from_token(
+        &delegate,
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(2.0),
+    )
+    .await
+    .unwrap();
+
+    let from_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &from.pubkey(),
+    )
+    .await;
+    let to_account = get_account(&mut context.banks_client, &program_id, &mint, &to.pubkey()).await;
+    let allowance = get_allowance(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &from.pubkey(),
+        &delegate.pubkey(),
+    )
+    .await;
+
+    assert_eq!(from_account.balance, sol(8.0));
+    assert_eq!(to_account.balance, sol(2.0));
+    assert_eq!(allowance.amount, sol(3.0));
+}
+
+#[tokio::test]
+async fn test_fail_insufficient_allowance_transfer_from() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let delegate = Keypair::new();
+    let to = Keypair::new();
+
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &delegate.pubkey(),
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    approve(
+        &from,
+        &delegate,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = transfer_from_token(
+        &delegate,
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(2.0),
+    )
+    .await;
+
+    match transaction_result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(error_code),
+        ))) => assert_eq!(error_code, SimpleTokenErrors::InsufficientAllowance as u32),
+        _ => panic!("Should fail"),
+    }
+}
+
+#[tokio::test]
+async fn test_fail_not_approved_transfer_from() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let delegate = Keypair::new();
+    let to = Keypair::new();
+
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &delegate.pubkey(),
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = transfer_from_token(
+        &delegate,
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await;
+
+    match transaction_result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(error_code),
+        ))) => assert_eq!(error_code, SimpleTokenErrors::InsufficientAllowance as u32),
+        _ => panic!("Should fail"),
+    }
+}
+
+#[tokio::test]
+async fn test_revoke() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let delegate = Keypair::new();
+
+    approve(
+        &owner,
+        &delegate,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(3.0),
+    )
+    .await
+    .unwrap();
+
+    revoke(
+        &owner,
+        &delegate,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    let allowance = get_allowance(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &owner.pubkey(),
+        &delegate.pubkey(),
+    )
+    .await;
+    assert_eq!(allowance.amount, 0);
+}
@@ -0,0 +1,191 @@
+use borsh::BorshSerialize;
+use simple_token::instructions as simple_token_instructions;
+use solana_program::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    system_program,
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+mod helpers;
+use crate::helpers::{
+    batch_mint, batch_transfer, find_config_pda, find_user_pda, get_account, get_config,
+    initialize, mint_to, prepare, process_transaction, sol, transfer_sol,
+};
+
+#[tokio::test]
+async fn test_batch_mint() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let recipients = vec![
+        (Pubkey::new_unique(), sol(1.0)),
+        (Pubkey::new_unique(), sol(2.0)),
+        (Pubkey::new_unique(), sol(3.0)),
+    ];
+
+    batch_mint(
+        &owner,
+        &recipients,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    for (to, amount) in recipients.iter() {
+        let to_account = get_account(&mut context.banks_client, &program_id, &mint, to).await;
+        assert_eq!(to_account.balance, *amount);
+    }
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config.total_supply, sol(6.0));
+}
+
+#[tokio::test]
+async fn test_batch_transfer() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let recipients = vec![
+        (Pubkey::new_unique(), sol(1.0)),
+        (Pubkey::new_unique(), sol(2.0)),
+        (Pubkey::new_unique(), sol(3.0)),
+    ];
+
+    batch_transfer(
+        &from,
+        &recipients,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    for (to, amount) in recipients.iter() {
+        let to_account = get_account(&mut context.banks_client, &program_id, &mint, to).await;
+        assert_eq!(to_account.balance, *amount);
+    }
+    let from_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &from.pubkey(),
+    )
+    .await;
+    assert_eq!(from_account.balance, sol(4.0));
+}
+
+#[tokio::test]
+async fn test_fail_atomic_batch_transfer() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let first = Pubkey::new_unique();
+    let transaction_result = batch_transfer(
+        &from,
+        &[(first, sol(6.0)), (Pubkey::new_unique(), sol(6.0))],
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await;
+
+    assert!(transaction_result.is_err());
+    let from_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &from.pubkey(),
+    )
+    .await;
+    assert_eq!(from_account.balance, sol(10.0));
+    assert!(context
+        .banks_client
+        .get_account(find_user_pda(&program_id, &mint, &first))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_fail_missing_recipient_pda_batch_mint() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let first = Pubkey::new_unique();
+    let batch_mint_instruction = simple_token_instructions::Instruction::BatchMint {
+        mint,
+        recipients: vec![(first, sol(1.0)), (Pubkey::new_unique(), sol(1.0))],
+    };
+
+    let transaction_result = process_transaction(
+        &mut context.banks_client,
+        vec![Instruction::new_with_bytes(
+            program_id,
+            &batch_mint_instruction.try_to_vec().unwrap(),
+            vec![
+                AccountMeta::new(owner.pubkey(), true),
+                AccountMeta::new(find_config_pda(&program_id, &mint), false),
+                AccountMeta::new(system_program::id(), false),
+                AccountMeta::new(find_user_pda(&program_id, &mint, &first), false),
+            ],
+        )],
+        vec![&owner],
+    )
+    .await;
+
+    assert_eq!(
+        transaction_result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config.total_supply, 0);
+}
@@ -0,0 +1,149 @@
+use borsh::BorshSerialize;
+use simple_token::{errors::SimpleTokenErrors, instructions as simple_token_instructions};
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, burn_own, find_config_pda, find_user_pda, get_account, get_config,
+    initialize, mint_to, prepare, process_transaction, sol, transfer_sol,
+};
+
+#[tokio::test]
+async fn test_burn_own() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &holder.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    burn_own(
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+    )
+    .await
+    .unwrap();
+
+    let holder_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &holder.pubkey(),
+    )
+    .await;
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(holder_account.balance, sol(6.0));
+    assert_eq!(config.total_supply, sol(6.0));
+}
+
+#[tokio::test]
+async fn test_fail_underflow_burn_own() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &holder.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = burn_own(
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(2.0),
+    )
+    .await;
+
+    assert!(transaction_result.is_err());
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config.total_supply, sol(1.0));
+}
+
+#[tokio::test]
+async fn test_fail_other_holder_burn_own() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    let attacker = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &attacker.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let burn_own_instruction = simple_token_instructions::Instruction::BurnOwn {
+        mint,
+        amount: sol(1.0),
+    };
+
+    let transaction_result = process_transaction(
+        &mut context.banks_client,
+        vec![Instruction::new_with_bytes(
+            program_id,
+            &burn_own_instruction.try_to_vec().unwrap(),
+            vec![
+                AccountMeta::new(attacker.pubkey(), true),
+                AccountMeta::new(find_config_pda(&program_id, &mint), false),
+                AccountMeta::new(find_user_pda(&program_id, &mint, &holder.pubkey()), false),
+            ],
+        )],
+        vec![&attacker],
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidPda);
+}
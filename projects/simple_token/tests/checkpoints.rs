@@ -0,0 +1,274 @@
+use simple_token::{errors::SimpleTokenErrors, storage::MAX_CHECKPOINTS};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, balance_at, initialize, mint_to, prepare, sol, transfer_sol,
+    transfer_token,
+};
+
+#[tokio::test]
+async fn test_balance_at() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let to = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    context.warp_to_slot(10).unwrap();
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    context.warp_to_slot(20).unwrap();
+    transfer_token(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+    )
+    .await
+    .unwrap();
+
+    context.warp_to_slot(31).unwrap();
+    for (slot, from_balance, to_balance) in [
+        (5, 0.0, 0.0),
+        (10, 10.0, 0.0),
+        (15, 10.0, 0.0),
+        (20, 6.0, 4.0),
+        (30, 6.0, 4.0),
+    ] {
+        let from_balance_at = balance_at(
+            &owner,
+            &from.pubkey(),
+            &program_id,
+            &mint,
+            &mut context.banks_client,
+            slot,
+        )
+        .await
+        .unwrap();
+        let to_balance_at = balance_at(
+            &owner,
+            &to.pubkey(),
+            &program_id,
+            &mint,
+            &mut context.banks_client,
+            slot,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(from_balance_at, sol(from_balance));
+        assert_eq!(to_balance_at, sol(to_balance));
+    }
+}
+
+#[tokio::test]
+async fn test_balance_at_unknown_account() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    context.warp_to_slot(10).unwrap();
+
+    let balance = balance_at(
+        &owner,
+        &Keypair::new().pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        1,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(balance, 0);
+}
+
+#[tokio::test]
+async fn test_balance_at_full_checkpoints() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    for i in 0..MAX_CHECKPOINTS as u64 {
+        context.warp_to_slot(10 * (i + 1)).unwrap();
+        mint_to(
+            &owner,
+            &holder,
+            &program_id,
+            &mint,
+            &mut context.banks_client,
+            sol(1.0),
+        )
+        .await
+        .unwrap();
+    }
+    context
+        .warp_to_slot(10 * (MAX_CHECKPOINTS as u64 + 1))
+        .unwrap();
+
+    // every checkpoint is in use but none was overwritten, so the account was still empty
+    let balance = balance_at(
+        &owner,
+        &holder.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        5,
+    )
+    .await
+    .unwrap();
+    assert_eq!(balance, 0);
+}
+
+#[tokio::test]
+async fn test_fail_evicted_checkpoint_balance_at() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    for i in 0..=MAX_CHECKPOINTS as u64 {
+        context.warp_to_slot(10 * (i + 1)).unwrap();
+        mint_to(
+            &owner,
+            &holder,
+            &program_id,
+            &mint,
+            &mut context.banks_client,
+            sol(1.0),
+        )
+        .await
+        .unwrap();
+    }
+
+    // the first checkpoint at slot 10 got overwritten by the last one
+    let oldest_balance = balance_at(
+        &owner,
+        &holder.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        20,
+    )
+    .await
+    .unwrap();
+    assert_eq!(oldest_balance, sol(2.0));
+
+    let transaction_result = balance_at(
+        &owner,
+        &holder.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        15,
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::CheckpointUnavailable);
+}
+
+#[tokio::test]
+async fn test_fail_current_slot_balance_at() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    context.warp_to_slot(10).unwrap();
+    mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    // the balance of the current slot can still change
+    let transaction_result = balance_at(
+        &owner,
+        &owner.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        10,
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::CheckpointUnavailable);
+
+    let transaction_result = balance_at(
+        &owner,
+        &owner.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        u64::MAX,
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::CheckpointUnavailable);
+}
+
+#[tokio::test]
+async fn test_zero_transfers_keep_checkpoints() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    context.warp_to_slot(10).unwrap();
+    for (to, amount) in [(&owner, sol(1.0)), (&holder, sol(5.0))] {
+        mint_to(
+            &owner,
+            to,
+            &program_id,
+            &mint,
+            &mut context.banks_client,
+            amount,
+        )
+        .await
+        .unwrap();
+    }
+
+    // zero amount transfers in more slots than the ring holds don't evict the holder's history
+    for i in 0..=MAX_CHECKPOINTS as u64 {
+        context.warp_to_slot(20 + 10 * i).unwrap();
+        transfer_token(
+            &owner,
+            &holder,
+            &program_id,
+            &mint,
+            &mut context.banks_client,
+            0,
+        )
+        .await
+        .unwrap();
+    }
+
+    let balance = balance_at(
+        &owner,
+        &holder.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        10,
+    )
+    .await
+    .unwrap();
+    assert_eq!(balance, sol(5.0));
+}
@@ -0,0 +1,207 @@
+use simple_token::{client, errors::SimpleTokenErrors, events::Event};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, capture_events, emitted, force_transfer, get_account, initialize, mint_to,
+    prepare, process_transaction, set_frozen, sol, transfer_sol,
+};
+
+async fn create_holder(
+    context: &mut ProgramTestContext,
+    owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+) -> Keypair {
+    let holder = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &holder.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    mint_to(
+        owner,
+        &holder,
+        program_id,
+        mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    holder
+}
+
+#[tokio::test]
+async fn test_force_transfer() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    let holder = create_holder(&mut context, &owner, &program_id, &mint).await;
+    let treasury = Keypair::new();
+
+    force_transfer(
+        &owner,
+        &holder.pubkey(),
+        &treasury.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+    )
+    .await
+    .unwrap();
+
+    let holder_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &holder.pubkey(),
+    )
+    .await;
+    let treasury_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &treasury.pubkey(),
+    )
+    .await;
+    assert_eq!(holder_account.balance, sol(6.0));
+    assert_eq!(treasury_account.balance, sol(4.0));
+}
+
+#[tokio::test]
+async fn test_force_transfer_emits_event() {
+    let (mut context, program_id, owner) = prepare().await;
+    capture_events();
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    let holder = create_holder(&mut context, &owner, &program_id, &mint).await;
+    let treasury = Keypair::new();
+
+    force_transfer(
+        &owner,
+        &holder.pubkey(),
+        &treasury.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        emitted(&mint).last(),
+        Some(&Event::ForceTransfer {
+            mint,
+            authority: owner.pubkey(),
+            from: holder.pubkey(),
+            to: treasury.pubkey(),
+            amount: sol(4.0),
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_force_transfer_frozen_account() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    let holder = create_holder(&mut context, &owner, &program_id, &mint).await;
+    let treasury = Keypair::new();
+    set_frozen(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        true,
+    )
+    .await
+    .unwrap();
+
+    force_transfer(
+        &owner,
+        &holder.pubkey(),
+        &treasury.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let holder_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &holder.pubkey(),
+    )
+    .await;
+    assert_eq!(holder_account.balance, 0);
+    assert!(holder_account.frozen);
+}
+
+#[tokio::test]
+async fn test_fail_not_clawback_authority_force_transfer() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    let holder = create_holder(&mut context, &owner, &program_id, &mint).await;
+
+    let result = force_transfer(
+        &holder,
+        &owner.pubkey(),
+        &holder.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await;
+
+    assert_custom_error(result, SimpleTokenErrors::InvalidClawbackAuthority);
+}
+
+#[tokio::test]
+async fn test_fail_force_transfer_without_clawback_authority() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = Keypair::new();
+    process_transaction(
+        &mut context.banks_client,
+        vec![client::initialize(
+            &program_id,
+            &owner.pubkey(),
+            &mint.pubkey(),
+            &owner.pubkey(),
+            18,
+            Some(owner.pubkey()),
+            None,
+            false,
+            None,
+            String::from("Simple Token"),
+            String::from("SIMPLE"),
+            String::from(""),
+        )],
+        vec![&owner, &mint],
+    )
+    .await
+    .unwrap();
+    let holder = create_holder(&mut context, &owner, &program_id, &mint.pubkey()).await;
+
+    let result = force_transfer(
+        &owner,
+        &holder.pubkey(),
+        &owner.pubkey(),
+        &program_id,
+        &mint.pubkey(),
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await;
+
+    assert_custom_error(result, SimpleTokenErrors::InvalidClawbackAuthority);
+}
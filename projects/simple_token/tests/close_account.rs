@@ -0,0 +1,191 @@
+use simple_token::errors::SimpleTokenErrors;
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, burn_own, close_account, find_user_pda, get_account, initialize, mint_to,
+    prepare, set_frozen, sol, transfer_sol, transfer_token,
+};
+
+#[tokio::test]
+async fn test_close_account() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    let destination = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &holder.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    burn_own(
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    let holder_pda = find_user_pda(&program_id, &mint, &holder.pubkey());
+    let rent_lamports = context.banks_client.get_balance(holder_pda).await.unwrap();
+
+    close_account(
+        &holder,
+        &destination.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(holder_pda)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(destination.pubkey())
+            .await
+            .unwrap(),
+        rent_lamports
+    );
+
+    // the account can be recreated from scratch afterwards
+    mint_to(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(2.0),
+    )
+    .await
+    .unwrap();
+    let holder_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &holder.pubkey(),
+    )
+    .await;
+    assert_eq!(holder_account.balance, sol(2.0));
+}
+
+#[tokio::test]
+async fn test_fail_non_zero_balance_close_account() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &holder.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = close_account(
+        &holder,
+        &holder.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::NonZeroBalance);
+}
+
+#[tokio::test]
+async fn test_fail_frozen_close_account() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    let other = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &holder.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    transfer_token(
+        &holder,
+        &other,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    set_frozen(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        true,
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = close_account(
+        &holder,
+        &holder.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::AccountFrozen);
+}
@@ -0,0 +1,205 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::BorshSerialize;
+use simple_token::events::{decode_events, Event, EVENT_TAG};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    accept_ownership, burn_from, capture_events, emitted, initialize, mint_to, prepare,
+    propose_owner, sol, transfer_sol, transfer_token,
+};
+
+// program-test runs the program natively where sol_log_data only prints to stdout, so the log
+// lines the runtime would produce are built by hand here
+fn program_data(event: &Event) -> String {
+    format!(
+        "Program data: {} {}",
+        STANDARD.encode(EVENT_TAG),
+        STANDARD.encode(event.try_to_vec().unwrap())
+    )
+}
+
+#[test]
+fn test_decode_events() {
+    let program_id = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let from = Pubkey::new_unique();
+    let to = Pubkey::new_unique();
+
+    let events = vec![
+        Event::Initialize {
+            mint,
+            owner,
+            decimals: 9,
+        },
+        Event::Mint {
+            mint,
+            to: from,
+            amount: 10,
+        },
+        Event::Transfer {
+            mint,
+            from,
+            to,
+            amount: 4,
+            fee: 1,
+        },
+        Event::Burn {
+            mint,
+            from: to,
+            amount: 1,
+        },
+        Event::ChangeOwner {
+            mint,
+            previous_owner: owner,
+            new_owner: to,
+        },
+    ];
+
+    let mut logs = vec![format!("Program {} invoke [1]", program_id)];
+    for event in events.iter() {
+        logs.push(String::from("Program log: Instruction"));
+        logs.push(program_data(event));
+    }
+    logs.push(format!(
+        "Program {} consumed 100 of 200000 compute units",
+        program_id
+    ));
+    logs.push(format!("Program {} success", program_id));
+
+    assert_eq!(decode_events(&program_id, &logs), events);
+}
+
+#[test]
+fn test_decode_events_skips_other_programs() {
+    let program_id = Pubkey::new_unique();
+    let other_program_id = Pubkey::new_unique();
+    let event = Event::Mint {
+        mint: Pubkey::new_unique(),
+        to: Pubkey::new_unique(),
+        amount: 42,
+    };
+    let data = program_data(&event);
+
+    let logs = vec![
+        format!("Program {} invoke [1]", other_program_id),
+        data.clone(),
+        format!("Program {} invoke [2]", program_id),
+        String::from("Program log: success"),
+        data.clone(),
+        format!("Program {} success", program_id),
+        data.clone(),
+        format!("Program {} success", other_program_id),
+        format!("Program {} invoke [1]", program_id),
+        String::from("Program data: bm90IGFuIGV2ZW50"),
+        data.clone(),
+        format!("Program {} invoke [2]", other_program_id),
+        data.clone(),
+        format!(
+            "Program {} failed: custom program error: 0x1",
+            other_program_id
+        ),
+        format!("Program {} success", program_id),
+    ];
+
+    assert_eq!(
+        decode_events(&program_id, &logs),
+        vec![event.clone(), event]
+    );
+}
+
+#[tokio::test]
+async fn test_processor_emits_events() {
+    let (mut context, program_id, owner) = prepare().await;
+    capture_events();
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &holder.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(3.0),
+    )
+    .await
+    .unwrap();
+    transfer_token(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(2.0),
+    )
+    .await
+    .unwrap();
+    burn_from(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    propose_owner(
+        &owner,
+        &holder.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+    accept_ownership(&holder, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        emitted(&mint),
+        vec![
+            Event::Initialize {
+                mint,
+                owner: owner.pubkey(),
+                decimals: 18,
+            },
+            Event::Mint {
+                mint,
+                to: owner.pubkey(),
+                amount: sol(3.0),
+            },
+            Event::Transfer {
+                mint,
+                from: owner.pubkey(),
+                to: holder.pubkey(),
+                amount: sol(2.0),
+                fee: 0,
+            },
+            Event::Burn {
+                mint,
+                from: holder.pubkey(),
+                amount: sol(1.0),
+            },
+            Event::ChangeOwner {
+                mint,
+                previous_owner: owner.pubkey(),
+                new_owner: holder.pubkey(),
+            },
+        ]
+    );
+}
@@ -0,0 +1,276 @@
+use simple_token::errors::SimpleTokenErrors;
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, burn_from, get_account, initialize, mint_to, prepare, set_frozen, sol,
+    transfer_sol, transfer_token,
+};
+
+#[tokio::test]
+async fn test_freeze_account() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let user = Keypair::new();
+    mint_to(
+        &owner,
+        &user,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    set_frozen(
+        &owner,
+        &user,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        true,
+    )
+    .await
+    .unwrap();
+
+    let account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &user.pubkey(),
+    )
+    .await;
+    assert!(account.frozen);
+    assert_eq!(account.balance, sol(10.0));
+}
+
+#[tokio::test]
+async fn test_thaw_account() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let to = Keypair::new();
+
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    set_frozen(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        true,
+    )
+    .await
+    .unwrap();
+    set_frozen(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        false,
+    )
+    .await
+    .unwrap();
+
+    transfer_token(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(5.0),
+    )
+    .await
+    .unwrap();
+
+    let from_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &from.pubkey(),
+    )
+    .await;
+    assert!(!from_account.frozen);
+    assert_eq!(from_account.balance, sol(5.0));
+}
+
+#[tokio::test]
+async fn test_fail_not_freeze_authority_freeze() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let user = Keypair::new();
+    let not_freeze_authority = Keypair::new();
+
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &not_freeze_authority.pubkey(),
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = set_frozen(
+        &not_freeze_authority,
+        &user,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        true,
+    )
+    .await;
+
+    assert_custom_error(
+        transaction_result,
+        SimpleTokenErrors::InvalidFreezeAuthority,
+    );
+}
+
+#[tokio::test]
+async fn test_fail_frozen_transfer() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let to = Keypair::new();
+
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    set_frozen(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        true,
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = transfer_token(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(5.0),
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::AccountFrozen);
+}
+
+#[tokio::test]
+async fn test_fail_frozen_mint() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let to = Keypair::new();
+
+    set_frozen(
+        &owner,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        true,
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = mint_to(
+        &owner,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::AccountFrozen);
+}
+
+#[tokio::test]
+async fn test_fail_frozen_burn() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    set_frozen(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        true,
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = burn_from(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::AccountFrozen);
+}
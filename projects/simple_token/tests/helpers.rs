@@ -0,0 +1,1355 @@
+#![allow(dead_code)]
+
+use std::sync::{Mutex, Once};
+
+use borsh::BorshDeserialize;
+use simple_token::{
+    client,
+    errors::SimpleTokenErrors,
+    events::{Event, EVENT_TAG},
+    process_instruction,
+    staking::{StakeAccount, StakePool},
+    storage::{Account, Allowance, Config, PermitNonce, Role, TransferFee},
+    vesting::VestingSchedule,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    program_pack::Pack,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    system_instruction,
+};
+use solana_program_test::{
+    processor, BanksClient, BanksClientError, ProgramTest, ProgramTestContext,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    commitment_config::CommitmentLevel,
+    ed25519_instruction::new_ed25519_instruction,
+    signature::{Keypair, Signature, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+pub fn sol(amount: f64) -> u64 {
+    (amount * LAMPORTS_PER_SOL as f64) as u64
+}
+
+pub async fn process_transaction(
+    client: &mut BanksClient,
+    instructions: Vec<Instruction>,
+    signers: Vec<&Keypair>,
+) -> Result<Signature, BanksClientError> {
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&signers[0].pubkey()));
+    tx.sign(&signers, client.get_latest_blockhash().await?);
+    let sig = tx.signatures[0];
+    let result = client.process_transaction(tx).await;
+
+    match result {
+        Err(_) => Err(result.unwrap_err()),
+        Ok(_) => Ok(sig),
+    }
+}
+
+pub async fn transfer_sol(
+    context: &mut BanksClient,
+    payer: &Keypair,
+    receiver: &Pubkey,
+    amount: u64,
+) -> Result<Signature, BanksClientError> {
+    let ixs = vec![system_instruction::transfer(
+        &payer.pubkey(),
+        receiver,
+        amount,
+    )];
+    process_transaction(context, ixs, vec![payer]).await
+}
+
+pub async fn prepare() -> (ProgramTestContext, Pubkey, Keypair) {
+    let program_id = Pubkey::new_unique();
+    let (context, owner) = start(program_test(&program_id)).await;
+
+    (context, program_id, owner)
+}
+
+// tests which need additional programs or accounts extend this before calling start
+pub fn program_test(program_id: &Pubkey) -> ProgramTest {
+    let mut program_test =
+        ProgramTest::new("simple_token", *program_id, processor!(process_instruction));
+    // run the token program natively so wrapping uses the same spl-token version as the program
+    program_test.add_program(
+        "spl_token",
+        spl_token::id(),
+        processor!(spl_token::processor::Processor::process),
+    );
+    program_test
+}
+
+pub async fn start(program_test: ProgramTest) -> (ProgramTestContext, Keypair) {
+    let mut context = program_test.start_with_context().await;
+
+    let owner = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &owner.pubkey(),
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    (context, owner)
+}
+
+static EMITTED: Mutex<Vec<Event>> = Mutex::new(Vec::new());
+static CAPTURE: Once = Once::new();
+
+// records every event the program emits and hands all syscalls on to program-test's stubs
+struct CapturingStubs {
+    inner: Box<dyn SyscallStubs>,
+}
+
+impl SyscallStubs for CapturingStubs {
+    fn sol_log(&self, message: &str) {
+        self.inner.sol_log(message)
+    }
+    fn sol_log_compute_units(&self) {
+        self.inner.sol_log_compute_units()
+    }
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.inner
+            .sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_clock_sysvar(var_addr)
+    }
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_epoch_schedule_sysvar(var_addr)
+    }
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_fees_sysvar(var_addr)
+    }
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_rent_sysvar(var_addr)
+    }
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.inner.sol_get_return_data()
+    }
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.inner.sol_set_return_data(data)
+    }
+    fn sol_get_stack_height(&self) -> u64 {
+        self.inner.sol_get_stack_height()
+    }
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        if let [tag, event] = fields {
+            if *tag == EVENT_TAG {
+                EMITTED
+                    .lock()
+                    .unwrap()
+                    .push(Event::try_from_slice(event).unwrap());
+            }
+        }
+        self.inner.sol_log_data(fields)
+    }
+}
+
+// program-test runs the program natively and its stubs print sol_log_data to stdout instead of
+// the transaction logs, so the stubs it installed on start are wrapped to capture the events
+pub fn capture_events() {
+    CAPTURE.call_once(|| {
+        let inner = set_syscall_stubs(Box::new(Placeholder));
+        set_syscall_stubs(Box::new(CapturingStubs { inner }));
+    });
+}
+
+struct Placeholder;
+impl SyscallStubs for Placeholder {}
+
+// the stubs are shared by every test in this binary, each test only looks at its own mint
+pub fn emitted(mint: &Pubkey) -> Vec<Event> {
+    EMITTED
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|event| match event {
+            Event::Initialize { mint: m, .. }
+            | Event::Mint { mint: m, .. }
+            | Event::Transfer { mint: m, .. }
+            | Event::Burn { mint: m, .. }
+            | Event::ChangeOwner { mint: m, .. }
+            | Event::ForceTransfer { mint: m, .. }
+            | Event::Memo { mint: m, .. } => m == mint,
+        })
+        .cloned()
+        .collect()
+}
+
+#[allow(unused_imports)]
+pub use simple_token::client::{
+    find_allowance_pda, find_config_pda, find_fee_vault_pda, find_permit_nonce_pda, find_stake_pda,
+    find_stake_pool_pda, find_user_pda, find_vesting_pda,
+};
+
+pub async fn find_transfer_fee_vault(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+) -> Pubkey {
+    let config = get_config(banks_client, program_id, mint).await;
+    client::find_transfer_fee_vault(program_id, mint, &config)
+}
+
+pub async fn find_transfer_hook_accounts(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+) -> Vec<AccountMeta> {
+    let config = get_config(banks_client, program_id, mint).await;
+    client::transfer_hook_accounts(&config, &[])
+}
+
+pub async fn get_config(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+) -> Config {
+    let config_pda = find_config_pda(program_id, mint);
+
+    let config_account = banks_client
+        .get_account_with_commitment(config_pda, CommitmentLevel::Finalized)
+        .await
+        .unwrap()
+        .unwrap();
+
+    client::deserialize_config(config_account.data()).unwrap()
+}
+
+pub async fn get_account(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    account_pub_key: &Pubkey,
+) -> Account {
+    let to_pda = find_user_pda(program_id, mint, account_pub_key);
+
+    let account_account = banks_client
+        .get_account_with_commitment(to_pda, CommitmentLevel::Finalized)
+        .await
+        .unwrap()
+        .unwrap();
+
+    client::deserialize_account(account_account.data()).unwrap()
+}
+
+pub async fn get_allowance(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+) -> Allowance {
+    let allowance_pda = find_allowance_pda(program_id, mint, owner, delegate);
+
+    let allowance_account = banks_client
+        .get_account_with_commitment(allowance_pda, CommitmentLevel::Finalized)
+        .await
+        .unwrap()
+        .unwrap();
+
+    Allowance::try_from_slice(allowance_account.data()).unwrap()
+}
+
+pub async fn get_permit_nonce(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> u64 {
+    let permit_nonce_pda = find_permit_nonce_pda(program_id, mint, owner);
+
+    let permit_nonce_account = banks_client
+        .get_account_with_commitment(permit_nonce_pda, CommitmentLevel::Finalized)
+        .await
+        .unwrap()
+        .unwrap();
+
+    PermitNonce::try_from_slice(permit_nonce_account.data())
+        .unwrap()
+        .nonce
+}
+
+pub async fn initialize(
+    owner: &Keypair,
+    program_id: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Pubkey {
+    initialize_with_max_supply(owner, program_id, banks_client, None).await
+}
+
+pub async fn initialize_with_max_supply(
+    owner: &Keypair,
+    program_id: &Pubkey,
+    banks_client: &mut BanksClient,
+    max_supply: Option<u64>,
+) -> Pubkey {
+    let mint = Keypair::new();
+    initialize_mint(owner, &mint, program_id, banks_client, max_supply)
+        .await
+        .unwrap();
+
+    mint.pubkey()
+}
+
+pub async fn initialize_non_transferable(
+    owner: &Keypair,
+    program_id: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Pubkey {
+    let mint = Keypair::new();
+    initialize_mint_with(owner, &mint, program_id, banks_client, None, true)
+        .await
+        .unwrap();
+
+    mint.pubkey()
+}
+
+pub async fn initialize_mint(
+    owner: &Keypair,
+    mint: &Keypair,
+    program_id: &Pubkey,
+    banks_client: &mut BanksClient,
+    max_supply: Option<u64>,
+) -> Result<Signature, BanksClientError> {
+    initialize_mint_with(owner, mint, program_id, banks_client, max_supply, false).await
+}
+
+async fn initialize_mint_with(
+    owner: &Keypair,
+    mint: &Keypair,
+    program_id: &Pubkey,
+    banks_client: &mut BanksClient,
+    max_supply: Option<u64>,
+    non_transferable: bool,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::initialize(
+            program_id,
+            &owner.pubkey(),
+            &mint.pubkey(),
+            &owner.pubkey(),
+            18,
+            Some(owner.pubkey()),
+            Some(owner.pubkey()),
+            non_transferable,
+            max_supply,
+            String::from("Simple Token"),
+            String::from("SIMPLE"),
+            String::from("https://example.com/simple.json"),
+        )],
+        vec![owner, mint],
+    )
+    .await
+}
+
+pub async fn mint_to(
+    owner: &Keypair,
+    to: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::mint(
+            program_id,
+            &owner.pubkey(),
+            mint,
+            &to.pubkey(),
+            amount,
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+pub async fn batch_mint(
+    owner: &Keypair,
+    recipients: &[(Pubkey, u64)],
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::batch_mint(
+            program_id,
+            &owner.pubkey(),
+            mint,
+            recipients.to_vec(),
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+pub async fn batch_transfer(
+    from: &Keypair,
+    recipients: &[(Pubkey, u64)],
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Result<Signature, BanksClientError> {
+    let fee_vault_pda = find_transfer_fee_vault(banks_client, program_id, mint).await;
+    let transfer_hook_accounts = find_transfer_hook_accounts(banks_client, program_id, mint).await;
+
+    process_transaction(
+        banks_client,
+        vec![client::batch_transfer(
+            program_id,
+            &from.pubkey(),
+            mint,
+            recipients.to_vec(),
+            &fee_vault_pda,
+            &transfer_hook_accounts,
+        )],
+        vec![from],
+    )
+    .await
+}
+
+pub async fn transfer_token(
+    from: &Keypair,
+    to: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+) -> Result<Signature, BanksClientError> {
+    let fee_vault_pda = find_transfer_fee_vault(banks_client, program_id, mint).await;
+    let transfer_hook_accounts = find_transfer_hook_accounts(banks_client, program_id, mint).await;
+
+    process_transaction(
+        banks_client,
+        vec![client::transfer(
+            program_id,
+            &from.pubkey(),
+            mint,
+            &to.pubkey(),
+            amount,
+            &fee_vault_pda,
+            &transfer_hook_accounts,
+        )],
+        vec![from],
+    )
+    .await
+}
+
+pub async fn transfer_checked_token(
+    from: &Keypair,
+    to: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+    decimals: u8,
+) -> Result<Signature, BanksClientError> {
+    let fee_vault_pda = find_transfer_fee_vault(banks_client, program_id, mint).await;
+    let transfer_hook_accounts = find_transfer_hook_accounts(banks_client, program_id, mint).await;
+
+    process_transaction(
+        banks_client,
+        vec![client::transfer_checked(
+            program_id,
+            &from.pubkey(),
+            mint,
+            &to.pubkey(),
+            amount,
+            decimals,
+            &fee_vault_pda,
+            &transfer_hook_accounts,
+        )],
+        vec![from],
+    )
+    .await
+}
+
+// memo_instruction is logged through SPL Memo right before the transfer
+#[allow(clippy::too_many_arguments)]
+pub async fn transfer_with_memo(
+    from: &Keypair,
+    to: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+    memo: &str,
+    memo_instruction: Option<&str>,
+) -> Result<Signature, BanksClientError> {
+    let fee_vault_pda = find_transfer_fee_vault(banks_client, program_id, mint).await;
+    let transfer_hook_accounts = find_transfer_hook_accounts(banks_client, program_id, mint).await;
+
+    let mut instructions: Vec<Instruction> =
+        memo_instruction.map(client::memo).into_iter().collect();
+    instructions.push(client::transfer_with_memo(
+        program_id,
+        &from.pubkey(),
+        mint,
+        &to.pubkey(),
+        amount,
+        memo.to_string(),
+        &fee_vault_pda,
+        &transfer_hook_accounts,
+    ));
+    process_transaction(banks_client, instructions, vec![from]).await
+}
+
+pub async fn burn_from(
+    owner: &Keypair,
+    from: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::burn(
+            program_id,
+            &owner.pubkey(),
+            mint,
+            &from.pubkey(),
+            amount,
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+pub async fn burn_own(
+    holder: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::burn_own(program_id, &holder.pubkey(), mint, amount)],
+        vec![holder],
+    )
+    .await
+}
+
+pub async fn close_account(
+    holder: &Keypair,
+    destination: &Pubkey,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::close_account(
+            program_id,
+            &holder.pubkey(),
+            mint,
+            destination,
+        )],
+        vec![holder],
+    )
+    .await
+}
+
+pub async fn propose_owner(
+    owner: &Keypair,
+    new_owner: &Pubkey,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::propose_owner(
+            program_id,
+            &owner.pubkey(),
+            mint,
+            new_owner,
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+pub async fn accept_ownership(
+    new_owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::accept_ownership(
+            program_id,
+            &new_owner.pubkey(),
+            mint,
+        )],
+        vec![new_owner],
+    )
+    .await
+}
+
+pub async fn cancel_ownership_transfer(
+    owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::cancel_ownership_transfer(
+            program_id,
+            &owner.pubkey(),
+            mint,
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+pub async fn grant_role(
+    authority: &Keypair,
+    role: Role,
+    holder: &Pubkey,
+    mint_quota: Option<u64>,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::grant_role(
+            program_id,
+            &authority.pubkey(),
+            mint,
+            role,
+            holder,
+            mint_quota,
+        )],
+        vec![authority],
+    )
+    .await
+}
+
+pub async fn revoke_role(
+    authority: &Keypair,
+    role: Role,
+    holder: &Pubkey,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::revoke_role(
+            program_id,
+            &authority.pubkey(),
+            mint,
+            role,
+            holder,
+        )],
+        vec![authority],
+    )
+    .await
+}
+
+pub async fn set_paused(
+    pauser: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    paused: bool,
+) -> Result<Signature, BanksClientError> {
+    let pause_instruction = if paused {
+        client::pause(program_id, &pauser.pubkey(), mint)
+    } else {
+        client::unpause(program_id, &pauser.pubkey(), mint)
+    };
+
+    process_transaction(banks_client, vec![pause_instruction], vec![pauser]).await
+}
+
+pub async fn approve(
+    owner: &Keypair,
+    delegate: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::approve(
+            program_id,
+            &owner.pubkey(),
+            mint,
+            &delegate.pubkey(),
+            amount,
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+pub async fn revoke(
+    owner: &Keypair,
+    delegate: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::revoke(
+            program_id,
+            &owner.pubkey(),
+            mint,
+            &delegate.pubkey(),
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+pub async fn transfer_from_token(
+    delegate: &Keypair,
+    from: &Keypair,
+    to: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+) -> Result<Signature, BanksClientError> {
+    let fee_vault_pda = find_transfer_fee_vault(banks_client, program_id, mint).await;
+    let transfer_hook_accounts = find_transfer_hook_accounts(banks_client, program_id, mint).await;
+
+    process_transaction(
+        banks_client,
+        vec![client::transfer_from(
+            program_id,
+            &delegate.pubkey(),
+            mint,
+            &from.pubkey(),
+            &to.pubkey(),
+            amount,
+            &fee_vault_pda,
+            &transfer_hook_accounts,
+        )],
+        vec![delegate],
+    )
+    .await
+}
+
+pub async fn set_frozen(
+    freeze_authority: &Keypair,
+    user: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    frozen: bool,
+) -> Result<Signature, BanksClientError> {
+    let freeze_instruction = if frozen {
+        client::freeze_account(program_id, &freeze_authority.pubkey(), mint, &user.pubkey())
+    } else {
+        client::thaw_account(program_id, &freeze_authority.pubkey(), mint, &user.pubkey())
+    };
+
+    process_transaction(
+        banks_client,
+        vec![freeze_instruction],
+        vec![freeze_authority],
+    )
+    .await
+}
+
+pub async fn force_transfer(
+    clawback_authority: &Keypair,
+    from: &Pubkey,
+    to: &Pubkey,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::force_transfer(
+            program_id,
+            &clawback_authority.pubkey(),
+            mint,
+            from,
+            to,
+            amount,
+        )],
+        vec![clawback_authority],
+    )
+    .await
+}
+
+pub async fn update_metadata(
+    owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::update_metadata(
+            program_id,
+            &owner.pubkey(),
+            mint,
+            name.to_string(),
+            symbol.to_string(),
+            uri.to_string(),
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+pub async fn set_transfer_fee(
+    owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    transfer_fee: Option<TransferFee>,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::set_transfer_fee(
+            program_id,
+            &owner.pubkey(),
+            mint,
+            transfer_fee,
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+pub async fn set_transfer_hook(
+    owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    transfer_hook_program: Option<Pubkey>,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::set_transfer_hook(
+            program_id,
+            &owner.pubkey(),
+            mint,
+            transfer_hook_program,
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+pub async fn set_memo_required(
+    owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    required: bool,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::set_memo_required(
+            program_id,
+            &owner.pubkey(),
+            mint,
+            required,
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+pub async fn withdraw_fees(
+    fee_collector: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::withdraw_fees(
+            program_id,
+            &fee_collector.pubkey(),
+            mint,
+        )],
+        vec![fee_collector],
+    )
+    .await
+}
+
+pub async fn get_vesting_schedule(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    beneficiary: &Pubkey,
+) -> VestingSchedule {
+    let vesting_pda = find_vesting_pda(program_id, mint, beneficiary);
+
+    let vesting_account = banks_client
+        .get_account_with_commitment(vesting_pda, CommitmentLevel::Finalized)
+        .await
+        .unwrap()
+        .unwrap();
+
+    VestingSchedule::try_from_slice(vesting_account.data()).unwrap()
+}
+
+// moves to the next slot as well so repeated transactions get a fresh blockhash
+pub async fn warp_to_timestamp(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.slot += 1;
+    clock.unix_timestamp = unix_timestamp;
+    context.warp_to_slot(clock.slot).unwrap();
+    context.set_sysvar(&clock);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn mint_vested(
+    minter: &Keypair,
+    to: &Pubkey,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+    start_ts: i64,
+    cliff_ts: i64,
+    end_ts: i64,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::mint_vested(
+            program_id,
+            &minter.pubkey(),
+            mint,
+            to,
+            amount,
+            start_ts,
+            cliff_ts,
+            end_ts,
+        )],
+        vec![minter],
+    )
+    .await
+}
+
+pub async fn claim_vested(
+    beneficiary: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::claim_vested(
+            program_id,
+            &beneficiary.pubkey(),
+            mint,
+        )],
+        vec![beneficiary],
+    )
+    .await
+}
+
+pub async fn fund_staking_rewards(
+    owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+    reward_rate: u64,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::fund_staking_rewards(
+            program_id,
+            &owner.pubkey(),
+            mint,
+            amount,
+            reward_rate,
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+pub async fn stake(
+    staker: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::stake(program_id, &staker.pubkey(), mint, amount)],
+        vec![staker],
+    )
+    .await
+}
+
+pub async fn unstake(
+    staker: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::unstake(program_id, &staker.pubkey(), mint, amount)],
+        vec![staker],
+    )
+    .await
+}
+
+pub async fn claim_rewards(
+    staker: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::claim_rewards(program_id, &staker.pubkey(), mint)],
+        vec![staker],
+    )
+    .await
+}
+
+pub async fn get_stake_pool(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+) -> StakePool {
+    let stake_pool_account = banks_client
+        .get_account_with_commitment(
+            find_stake_pool_pda(program_id, mint),
+            CommitmentLevel::Finalized,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    StakePool::try_from_slice(stake_pool_account.data()).unwrap()
+}
+
+pub async fn get_stake_account(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    staker: &Pubkey,
+) -> StakeAccount {
+    let stake_account = banks_client
+        .get_account_with_commitment(
+            find_stake_pda(program_id, mint, staker),
+            CommitmentLevel::Finalized,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    StakeAccount::try_from_slice(stake_account.data()).unwrap()
+}
+
+pub async fn create_airdrop(
+    owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    expiry_ts: i64,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::create_airdrop(
+            program_id,
+            &owner.pubkey(),
+            mint,
+            merkle_root,
+            total_amount,
+            expiry_ts,
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn claim_airdrop(
+    claimant: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    merkle_root: [u8; 32],
+    index: u64,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::claim_airdrop(
+            program_id,
+            &claimant.pubkey(),
+            mint,
+            merkle_root,
+            index,
+            amount,
+            proof,
+        )],
+        vec![claimant],
+    )
+    .await
+}
+
+pub async fn clawback_airdrop(
+    owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    merkle_root: [u8; 32],
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::clawback_airdrop(
+            program_id,
+            &owner.pubkey(),
+            mint,
+            merkle_root,
+        )],
+        vec![owner],
+    )
+    .await
+}
+
+// signer signs the permit message off-chain, it only differs from owner to forge permits
+#[allow(clippy::too_many_arguments)]
+pub async fn permit(
+    relayer: &Keypair,
+    signer: &Keypair,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+    nonce: u64,
+    expiry: i64,
+) -> Result<Signature, BanksClientError> {
+    let message = client::permit_message(program_id, mint, owner, delegate, amount, nonce, expiry);
+    let signer = ed25519_dalek::Keypair::from_bytes(&signer.to_bytes()).unwrap();
+
+    process_transaction(
+        banks_client,
+        vec![
+            new_ed25519_instruction(&signer, &message),
+            client::permit(
+                program_id,
+                &relayer.pubkey(),
+                mint,
+                owner,
+                delegate,
+                amount,
+                nonce,
+                expiry,
+            ),
+        ],
+        vec![relayer],
+    )
+    .await
+}
+
+pub async fn initialize_wrapped_mint(
+    payer: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::initialize_wrapped_mint(
+            program_id,
+            &payer.pubkey(),
+            mint,
+        )],
+        vec![payer],
+    )
+    .await
+}
+
+pub async fn create_wrapped_token_account(
+    owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+) -> Pubkey {
+    let token_account = Keypair::new();
+    let rent = banks_client.get_rent().await.unwrap();
+
+    process_transaction(
+        banks_client,
+        vec![
+            system_instruction::create_account(
+                &owner.pubkey(),
+                &token_account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account3(
+                &spl_token::id(),
+                &token_account.pubkey(),
+                &client::find_wrapped_mint(program_id, mint),
+                &owner.pubkey(),
+            )
+            .unwrap(),
+        ],
+        vec![owner, &token_account],
+    )
+    .await
+    .unwrap();
+
+    token_account.pubkey()
+}
+
+pub async fn get_token_account(
+    banks_client: &mut BanksClient,
+    token_account: &Pubkey,
+) -> spl_token::state::Account {
+    let account = banks_client
+        .get_account(*token_account)
+        .await
+        .unwrap()
+        .unwrap();
+
+    spl_token::state::Account::unpack(account.data()).unwrap()
+}
+
+pub async fn get_wrapped_mint(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+) -> spl_token::state::Mint {
+    let account = banks_client
+        .get_account(client::find_wrapped_mint(program_id, mint))
+        .await
+        .unwrap()
+        .unwrap();
+
+    spl_token::state::Mint::unpack(account.data()).unwrap()
+}
+
+pub async fn wrap(
+    holder: &Keypair,
+    token_account: &Pubkey,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+) -> Result<Signature, BanksClientError> {
+    process_transaction(
+        banks_client,
+        vec![client::wrap(
+            program_id,
+            &holder.pubkey(),
+            mint,
+            token_account,
+            amount,
+        )],
+        vec![holder],
+    )
+    .await
+}
+
+pub async fn unwrap(
+    holder: &Keypair,
+    token_account: &Pubkey,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    amount: u64,
+) -> Result<Signature, BanksClientError> {
+    let transfer_hook_accounts = find_transfer_hook_accounts(banks_client, program_id, mint).await;
+
+    process_transaction(
+        banks_client,
+        vec![client::unwrap(
+            program_id,
+            &holder.pubkey(),
+            mint,
+            token_account,
+            amount,
+            &transfer_hook_accounts,
+        )],
+        vec![holder],
+    )
+    .await
+}
+
+// read only instructions answer through the return data of a simulated transaction
+pub async fn balance_at(
+    payer: &Keypair,
+    user: &Pubkey,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    banks_client: &mut BanksClient,
+    slot: u64,
+) -> Result<u64, BanksClientError> {
+    let tx = Transaction::new_signed_with_payer(
+        &[client::balance_at(program_id, mint, user, slot)],
+        Some(&payer.pubkey()),
+        &[payer],
+        banks_client.get_latest_blockhash().await?,
+    );
+    let simulation = banks_client.simulate_transaction(tx).await?;
+    if let Some(Err(error)) = simulation.result {
+        return Err(BanksClientError::TransactionError(error));
+    }
+
+    let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+    Ok(u64::try_from_slice(&return_data.data).unwrap())
+}
+
+pub fn assert_custom_error<T>(
+    transaction_result: Result<T, BanksClientError>,
+    expected: SimpleTokenErrors,
+) {
+    match transaction_result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(error_code),
+        ))) => assert_eq!(error_code, expected as u32),
+        _ => panic!("Should fail"),
+    }
+}
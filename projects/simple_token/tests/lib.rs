@@ -1,266 +1,99 @@
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::BorshSerialize;
 use simple_token::{
-    errors::SimpleTokenErrors,
-    instructions as simple_token_instructions, process_instruction,
-    storage::{Account, Config},
+    client::find_wrapped_mint, errors::SimpleTokenErrors,
+    instructions as simple_token_instructions, storage::Config,
 };
 use solana_program::{
     instruction::{AccountMeta, Instruction, InstructionError},
-    native_token::LAMPORTS_PER_SOL,
     pubkey::Pubkey,
-    system_instruction, system_program,
-};
-use solana_program_test::{
-    processor,
-    tokio::{self},
-    BanksClient, BanksClientError, ProgramTest, ProgramTestContext,
+    system_program,
 };
+use solana_program_test::{tokio, BanksClientError};
 use solana_sdk::{
-    account::ReadableAccount,
-    commitment_config::CommitmentLevel,
-    signature::{Keypair, Signature, Signer},
-    transaction::{Transaction, TransactionError},
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
 };
 
-pub fn sol(amount: f64) -> u64 {
-    (amount * LAMPORTS_PER_SOL as f64) as u64
-}
-
-async fn process_transaction(
-    client: &mut BanksClient,
-    instructions: Vec<Instruction>,
-    signers: Vec<&Keypair>,
-) -> Result<Signature, BanksClientError> {
-    let mut tx = Transaction::new_with_payer(&instructions, Some(&signers[0].pubkey()));
-    tx.sign(&signers, client.get_latest_blockhash().await?);
-    let sig = tx.signatures[0];
-    let result = client.process_transaction(tx).await;
-
-    return match result {
-        Err(_) => Err(result.unwrap_err()),
-        Ok(_) => Ok(sig),
-    };
-}
+mod helpers;
+use crate::helpers::{
+    accept_ownership, burn_from, find_config_pda, find_transfer_fee_vault, find_user_pda,
+    get_account, get_config, get_wrapped_mint, initialize, initialize_mint,
+    initialize_wrapped_mint, mint_to, prepare, process_transaction, sol, transfer_sol,
+    transfer_token,
+};
 
-async fn transfer_sol(
-    context: &mut BanksClient,
-    payer: &Keypair,
-    receiver: &Pubkey,
-    amount: u64,
-) -> Result<Signature, BanksClientError> {
-    let ixs = vec![system_instruction::transfer(
-        &payer.pubkey(),
-        receiver,
-        amount,
-    )];
-    process_transaction(context, ixs, vec![payer]).await
+#[tokio::test]
+async fn test_initialize() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    let config_data: Config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config_data.decimals, 18);
+    assert_eq!(config_data.owner, owner.pubkey());
+    assert_eq!(config_data.freeze_authority, Some(owner.pubkey()));
+    assert_eq!(config_data.name, "Simple Token");
+    assert_eq!(config_data.symbol, "SIMPLE");
+    assert_eq!(config_data.uri, "https://example.com/simple.json");
+    assert_eq!(config_data.total_supply, 0);
+    assert_eq!(config_data.max_supply, None);
 }
 
-async fn prepare() -> (ProgramTestContext, Pubkey, Keypair) {
-    let program_id = Pubkey::new_unique();
-    let mut context = ProgramTest::new("simple_token", program_id, processor!(process_instruction))
-        .start_with_context()
-        .await;
+#[tokio::test]
+async fn test_pda_has_lamports_initialize() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = Keypair::new();
+    let config_pda = find_config_pda(&program_id, &mint.pubkey());
 
-    let owner = Keypair::new();
     transfer_sol(
         &mut context.banks_client,
         &context.payer,
-        &owner.pubkey(),
-        sol(10.0),
+        &config_pda,
+        sol(1.0),
     )
     .await
     .unwrap();
 
-    return (context, program_id, owner);
-}
-
-async fn get_config(banks_client: &mut BanksClient, program_id: &Pubkey) -> Config {
-    let (config_pda, _) = Pubkey::find_program_address(&[b"config"], program_id);
-
-    let config_account = banks_client
-        .get_account_with_commitment(config_pda, CommitmentLevel::Finalized)
-        .await
-        .unwrap()
-        .unwrap();
-
-    return Config::try_from_slice(config_account.data()).unwrap();
-}
-
-async fn get_account(
-    banks_client: &mut BanksClient,
-    program_id: &Pubkey,
-    account_pub_key: &Pubkey,
-) -> Account {
-    let (to_pda, _) = Pubkey::find_program_address(&[account_pub_key.as_ref()], &program_id);
-
-    let account_account = banks_client
-        .get_account_with_commitment(to_pda, CommitmentLevel::Finalized)
+    initialize_mint(&owner, &mint, &program_id, &mut context.banks_client, None)
         .await
-        .unwrap()
         .unwrap();
-
-    return Account::try_from_slice(account_account.data()).unwrap();
-}
-
-async fn initialize(owner: &Keypair, program_id: &Pubkey, banks_client: &mut BanksClient) {
-    let initialize_instruction = simple_token_instructions::Instruction::Initialize {
-        owner: owner.pubkey(),
-        decimals: 18,
-    };
-    let (config_pda, _) = Pubkey::find_program_address(&[b"config"], program_id);
-
-    process_transaction(
-        banks_client,
-        vec![Instruction::new_with_bytes(
-            *program_id,
-            &initialize_instruction.try_to_vec().unwrap(),
-            vec![
-                AccountMeta::new(owner.pubkey(), true),
-                AccountMeta::new(config_pda, false),
-                AccountMeta::new(system_program::id(), false),
-            ],
-        )],
-        vec![&owner],
-    )
-    .await
-    .unwrap();
-}
-
-async fn mint_to(
-    owner: &Keypair,
-    to: &Keypair,
-    program_id: &Pubkey,
-    banks_client: &mut BanksClient,
-    amount: u64,
-) -> Result<Signature, BanksClientError> {
-    let (to_pda, _) = Pubkey::find_program_address(&[to.pubkey().as_ref()], &program_id);
-    let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &program_id);
-
-    let mint_instruction = simple_token_instructions::Instruction::Mint {
-        to: to.pubkey(),
-        amount: amount,
-    };
-
-    process_transaction(
-        banks_client,
-        vec![Instruction::new_with_bytes(
-            *program_id,
-            &mint_instruction.try_to_vec().unwrap(),
-            vec![
-                AccountMeta::new(owner.pubkey(), true),
-                AccountMeta::new(config_pda, false),
-                AccountMeta::new(to_pda, false),
-                AccountMeta::new(system_program::id(), false),
-            ],
-        )],
-        vec![&owner],
-    )
-    .await
-}
-
-async fn transfer_token(
-    from: &Keypair,
-    to: &Keypair,
-    program_id: &Pubkey,
-    banks_client: &mut BanksClient,
-    amount: u64,
-) -> Result<Signature, BanksClientError> {
-    let (from_pda, _) = Pubkey::find_program_address(&[from.pubkey().as_ref()], &program_id);
-    let (to_pda, _) = Pubkey::find_program_address(&[to.pubkey().as_ref()], &program_id);
-
-    let transfer_instruction = simple_token_instructions::Instruction::Transfer {
-        to: to.pubkey(),
-        amount: amount,
-    };
-
-    process_transaction(
-        banks_client,
-        vec![Instruction::new_with_bytes(
-            *program_id,
-            &transfer_instruction.try_to_vec().unwrap(),
-            vec![
-                AccountMeta::new(from.pubkey(), true),
-                AccountMeta::new(from_pda, false),
-                AccountMeta::new(to_pda, false),
-                AccountMeta::new(system_program::id(), false),
-            ],
-        )],
-        vec![&from],
-    )
-    .await
-}
-
-async fn burn_from(
-    owner: &Keypair,
-    from: &Keypair,
-    program_id: &Pubkey,
-    banks_client: &mut BanksClient,
-    amount: u64,
-) -> Result<Signature, BanksClientError> {
-    let (from_pda, _) = Pubkey::find_program_address(&[from.pubkey().as_ref()], &program_id);
-    let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &program_id);
-
-    let burn_instruction = simple_token_instructions::Instruction::Burn {
-        from: from.pubkey(),
-        amount: amount,
-    };
-
-    process_transaction(
-        banks_client,
-        vec![Instruction::new_with_bytes(
-            *program_id,
-            &burn_instruction.try_to_vec().unwrap(),
-            vec![
-                AccountMeta::new(owner.pubkey(), true),
-                AccountMeta::new(config_pda, false),
-                AccountMeta::new(from_pda, false),
-                AccountMeta::new(system_program::id(), false),
-            ],
-        )],
-        vec![&owner],
-    )
-    .await
-}
-
-#[tokio::test]
-async fn test_initialize() {
-    let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
-    let config_data: Config = get_config(&mut context.banks_client, &program_id).await;
+    let config_data: Config =
+        get_config(&mut context.banks_client, &program_id, &mint.pubkey()).await;
     assert_eq!(config_data.decimals, 18);
     assert_eq!(config_data.owner, owner.pubkey());
 }
 
 #[tokio::test]
-async fn test_pda_has_lamports_initialize() {
+async fn test_pda_has_lamports_initialize_wrapped_mint() {
     let (mut context, program_id, owner) = prepare().await;
-    let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
+    // less than the rent of a mint, so the creation has to top it up
     transfer_sol(
         &mut context.banks_client,
         &context.payer,
-        &config_pda,
-        sol(1.0),
+        &find_wrapped_mint(&program_id, &mint),
+        sol(0.001),
     )
     .await
     .unwrap();
 
-    initialize(&owner, &program_id, &mut context.banks_client).await;
-    let config_data: Config = get_config(&mut context.banks_client, &program_id).await;
-    assert_eq!(config_data.decimals, 18);
-    assert_eq!(config_data.owner, owner.pubkey());
+    initialize_wrapped_mint(&owner, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    let wrapped_mint = get_wrapped_mint(&mut context.banks_client, &program_id, &mint).await;
+    assert!(wrapped_mint.is_initialized);
+    assert_eq!(wrapped_mint.decimals, 18);
 }
 
 #[tokio::test]
 async fn test_change_owner() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let new_owner = Keypair::new();
-    let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+    let config_pda = find_config_pda(&program_id, &mint);
 
-    let change_owner_instruction = simple_token_instructions::Instruction::ChangeOwner {
+    let change_owner_instruction = simple_token_instructions::Instruction::ProposeOwner {
+        mint,
         new_owner: new_owner.pubkey(),
     };
 
@@ -280,19 +113,36 @@ async fn test_change_owner() {
     .await
     .unwrap();
 
-    let config_data: Config = get_config(&mut context.banks_client, &program_id).await;
+    let config_data: Config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config_data.owner, owner.pubkey());
+    assert_eq!(config_data.pending_owner, Some(new_owner.pubkey()));
+
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &new_owner.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    accept_ownership(&new_owner, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+
+    let config_data: Config = get_config(&mut context.banks_client, &program_id, &mint).await;
     assert_eq!(config_data.decimals, 18);
     assert_eq!(config_data.owner, new_owner.pubkey());
+    assert_eq!(config_data.pending_owner, None);
 }
 
 #[tokio::test]
 async fn test_fail_not_owner_change_owner() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let new_owner = Keypair::new();
     let not_owner = Keypair::new();
-    let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+    let config_pda = find_config_pda(&program_id, &mint);
 
     transfer_sol(
         &mut context.banks_client,
@@ -303,7 +153,8 @@ async fn test_fail_not_owner_change_owner() {
     .await
     .unwrap();
 
-    let change_owner_instruction = simple_token_instructions::Instruction::ChangeOwner {
+    let change_owner_instruction = simple_token_instructions::Instruction::ProposeOwner {
+        mint,
         new_owner: new_owner.pubkey(),
     };
 
@@ -333,12 +184,13 @@ async fn test_fail_not_owner_change_owner() {
 #[tokio::test]
 async fn test_fail_invalid_pda_change_owner() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let new_owner = Keypair::new();
     let (not_config_pda, _) = Pubkey::find_program_address(&[b"not_config"], &program_id);
 
-    let change_owner_instruction = simple_token_instructions::Instruction::ChangeOwner {
+    let change_owner_instruction = simple_token_instructions::Instruction::ProposeOwner {
+        mint,
         new_owner: new_owner.pubkey(),
     };
 
@@ -369,7 +221,7 @@ async fn test_fail_invalid_pda_change_owner() {
 #[tokio::test]
 async fn test_mint() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let to = Keypair::new();
 
@@ -377,20 +229,21 @@ async fn test_mint() {
         &owner,
         &to,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(10.0),
     )
     .await
     .unwrap();
 
-    let account = get_account(&mut context.banks_client, &program_id, &to.pubkey()).await;
+    let account = get_account(&mut context.banks_client, &program_id, &mint, &to.pubkey()).await;
     assert_eq!(account.balance, sol(10.0));
 }
 
 #[tokio::test]
 async fn test_fail_not_owner_mint() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let not_owner = Keypair::new();
     let to = Keypair::new();
@@ -408,6 +261,7 @@ async fn test_fail_not_owner_mint() {
         &not_owner,
         &to,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(10.0),
     )
@@ -425,13 +279,14 @@ async fn test_fail_not_owner_mint() {
 #[tokio::test]
 async fn test_fail_invalid_config_pda_mint() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let to = Keypair::new();
-    let (to_pda, _) = Pubkey::find_program_address(&[to.pubkey().as_ref()], &program_id);
+    let to_pda = find_user_pda(&program_id, &mint, &to.pubkey());
     let (not_config_pda, _) = Pubkey::find_program_address(&[b"not_config"], &program_id);
 
     let mint_instruction = simple_token_instructions::Instruction::Mint {
+        mint,
         to: to.pubkey(),
         amount: sol(10.0),
     };
@@ -464,13 +319,14 @@ async fn test_fail_invalid_config_pda_mint() {
 #[tokio::test]
 async fn test_fail_invalid_to_pda_mint() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let to = Keypair::new();
-    let (to_pda, _) = Pubkey::find_program_address(&[owner.pubkey().as_ref()], &program_id);
-    let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+    let to_pda = find_user_pda(&program_id, &mint, &owner.pubkey());
+    let config_pda = find_config_pda(&program_id, &mint);
 
     let mint_instruction = simple_token_instructions::Instruction::Mint {
+        mint,
         to: to.pubkey(),
         amount: sol(10.0),
     };
@@ -503,7 +359,7 @@ async fn test_fail_invalid_to_pda_mint() {
 #[tokio::test]
 async fn test_fail_overflow_mint() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let from = Keypair::new();
 
@@ -520,6 +376,7 @@ async fn test_fail_overflow_mint() {
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(5.0),
     )
@@ -530,6 +387,7 @@ async fn test_fail_overflow_mint() {
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(u64::MAX as f64),
     )
@@ -547,7 +405,7 @@ async fn test_fail_overflow_mint() {
 #[tokio::test]
 async fn test_transfer() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let from = Keypair::new();
     let to = Keypair::new();
@@ -565,18 +423,38 @@ async fn test_transfer() {
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(10.0),
     )
     .await
     .unwrap();
 
-    transfer_token(&from, &to, &program_id, &mut context.banks_client, sol(5.0))
-        .await
-        .unwrap();
+    transfer_token(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(5.0),
+    )
+    .await
+    .unwrap();
 
-    let from_account = get_account(&mut context.banks_client, &program_id, &from.pubkey()).await;
-    let to_account = get_account(&mut context.banks_client, &program_id, &from.pubkey()).await;
+    let from_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &from.pubkey(),
+    )
+    .await;
+    let to_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &from.pubkey(),
+    )
+    .await;
 
     assert_eq!(from_account.balance, sol(5.0));
     assert_eq!(to_account.balance, sol(5.0));
@@ -585,7 +463,7 @@ async fn test_transfer() {
 #[tokio::test]
 async fn test_fail_not_enough_funds_transfer() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let from = Keypair::new();
     let to = Keypair::new();
@@ -603,14 +481,22 @@ async fn test_fail_not_enough_funds_transfer() {
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(1.0),
     )
     .await
     .unwrap();
 
-    let transaction_result =
-        transfer_token(&from, &to, &program_id, &mut context.banks_client, sol(5.0)).await;
+    let transaction_result = transfer_token(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(5.0),
+    )
+    .await;
 
     match transaction_result {
         Err(BanksClientError::TransactionError(TransactionError::InstructionError(
@@ -624,7 +510,7 @@ async fn test_fail_not_enough_funds_transfer() {
 #[tokio::test]
 async fn test_invalid_from_pda_transfer() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let from = Keypair::new();
     let to = Keypair::new();
@@ -642,15 +528,20 @@ async fn test_invalid_from_pda_transfer() {
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(1.0),
     )
     .await
     .unwrap();
 
-    let (to_pda, _) = Pubkey::find_program_address(&[to.pubkey().as_ref()], &program_id);
+    let to_pda = find_user_pda(&program_id, &mint, &to.pubkey());
+
+    let fee_vault_pda =
+        find_transfer_fee_vault(&mut context.banks_client, &program_id, &mint).await;
 
     let transfer_instruction = simple_token_instructions::Instruction::Transfer {
+        mint,
         to: to.pubkey(),
         amount: sol(5.0),
     };
@@ -662,8 +553,10 @@ async fn test_invalid_from_pda_transfer() {
             &transfer_instruction.try_to_vec().unwrap(),
             vec![
                 AccountMeta::new(from.pubkey(), true),
+                AccountMeta::new(find_config_pda(&program_id, &mint), false),
                 AccountMeta::new(to_pda, false),
                 AccountMeta::new(to_pda, false),
+                AccountMeta::new(fee_vault_pda, false),
                 AccountMeta::new(system_program::id(), false),
             ],
         )],
@@ -683,7 +576,7 @@ async fn test_invalid_from_pda_transfer() {
 #[tokio::test]
 async fn test_invalid_to_pda_transfer() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let from = Keypair::new();
     let to = Keypair::new();
@@ -701,15 +594,20 @@ async fn test_invalid_to_pda_transfer() {
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(1.0),
     )
     .await
     .unwrap();
 
-    let (from_pda, _) = Pubkey::find_program_address(&[from.pubkey().as_ref()], &program_id);
+    let from_pda = find_user_pda(&program_id, &mint, &from.pubkey());
+
+    let fee_vault_pda =
+        find_transfer_fee_vault(&mut context.banks_client, &program_id, &mint).await;
 
     let transfer_instruction = simple_token_instructions::Instruction::Transfer {
+        mint,
         to: to.pubkey(),
         amount: sol(5.0),
     };
@@ -721,8 +619,10 @@ async fn test_invalid_to_pda_transfer() {
             &transfer_instruction.try_to_vec().unwrap(),
             vec![
                 AccountMeta::new(from.pubkey(), true),
+                AccountMeta::new(find_config_pda(&program_id, &mint), false),
                 AccountMeta::new(from_pda, false),
                 AccountMeta::new(from_pda, false),
+                AccountMeta::new(fee_vault_pda, false),
                 AccountMeta::new(system_program::id(), false),
             ],
         )],
@@ -742,7 +642,7 @@ async fn test_invalid_to_pda_transfer() {
 #[tokio::test]
 async fn test_fail_overflow_transfer() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let from = Keypair::new();
     let to = Keypair::new();
@@ -760,28 +660,34 @@ async fn test_fail_overflow_transfer() {
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(u64::MAX as f64),
     )
     .await
     .unwrap();
 
-    transfer_token(&from, &to, &program_id, &mut context.banks_client, sol(5.0))
-        .await
-        .unwrap();
-
-    mint_to(
-        &owner,
+    transfer_token(
         &from,
+        &to,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(5.0),
     )
     .await
     .unwrap();
 
-    let transaction_result =
-        transfer_token(&from, &to, &program_id, &mut context.banks_client, u64::MAX).await;
+    // balances can't overflow anymore as the total supply is bounded by u64::MAX
+    let transaction_result = mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(5.0),
+    )
+    .await;
 
     match transaction_result {
         Err(BanksClientError::TransactionError(TransactionError::InstructionError(
@@ -795,13 +701,14 @@ async fn test_fail_overflow_transfer() {
 #[tokio::test]
 async fn test_burn() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let from = Keypair::new();
     mint_to(
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(10.0),
     )
@@ -812,20 +719,27 @@ async fn test_burn() {
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(1.0),
     )
     .await
     .unwrap();
 
-    let from_account = get_account(&mut context.banks_client, &program_id, &from.pubkey()).await;
+    let from_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &from.pubkey(),
+    )
+    .await;
     assert_eq!(from_account.balance, sol(9.0));
 }
 
 #[tokio::test]
 async fn test_fail_not_owner_burn() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let from = Keypair::new();
     let not_owner = Keypair::new();
@@ -843,6 +757,7 @@ async fn test_fail_not_owner_burn() {
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(10.0),
     )
@@ -853,6 +768,7 @@ async fn test_fail_not_owner_burn() {
         &not_owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(1.0),
     )
@@ -870,17 +786,18 @@ async fn test_fail_not_owner_burn() {
 #[tokio::test]
 async fn test_fail_invalid_config_pda_burn() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let from = Keypair::new();
 
-    let (from_pda, _) = Pubkey::find_program_address(&[from.pubkey().as_ref()], &program_id);
+    let from_pda = find_user_pda(&program_id, &mint, &from.pubkey());
     let (not_config, _) = Pubkey::find_program_address(&[b"not_config"], &program_id);
 
     mint_to(
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(10.0),
     )
@@ -888,6 +805,7 @@ async fn test_fail_invalid_config_pda_burn() {
     .unwrap();
 
     let burn_instruction = simple_token_instructions::Instruction::Burn {
+        mint,
         from: from.pubkey(),
         amount: sol(10.0),
     };
@@ -920,17 +838,18 @@ async fn test_fail_invalid_config_pda_burn() {
 #[tokio::test]
 async fn test_fail_invalid_from_pda_burn() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let from = Keypair::new();
 
-    let (from_pda, _) = Pubkey::find_program_address(&[owner.pubkey().as_ref()], &program_id);
-    let (not_config, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+    let from_pda = find_user_pda(&program_id, &mint, &owner.pubkey());
+    let not_config = find_config_pda(&program_id, &mint);
 
     mint_to(
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(10.0),
     )
@@ -938,6 +857,7 @@ async fn test_fail_invalid_from_pda_burn() {
     .unwrap();
 
     let burn_instruction = simple_token_instructions::Instruction::Burn {
+        mint,
         from: from.pubkey(),
         amount: sol(10.0),
     };
@@ -970,13 +890,14 @@ async fn test_fail_invalid_from_pda_burn() {
 #[tokio::test]
 async fn test_fail_underflow_burn() {
     let (mut context, program_id, owner) = prepare().await;
-    initialize(&owner, &program_id, &mut context.banks_client).await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
 
     let from = Keypair::new();
     mint_to(
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(1.0),
     )
@@ -987,6 +908,7 @@ async fn test_fail_underflow_burn() {
         &owner,
         &from,
         &program_id,
+        &mint,
         &mut context.banks_client,
         sol(10.0),
     )
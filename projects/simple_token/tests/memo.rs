@@ -0,0 +1,281 @@
+use simple_token::{
+    client,
+    errors::SimpleTokenErrors,
+    memo::{MAX_MEMO_LENGTH, MEMO_PROGRAM_ID},
+};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+use solana_program_test::{processor, tokio, ProgramTestContext};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod helpers;
+use crate::helpers::{
+    approve, assert_custom_error, batch_transfer, find_transfer_fee_vault, get_account, initialize,
+    mint_to, program_test, set_memo_required, sol, start, transfer_checked_token,
+    transfer_from_token, transfer_sol, transfer_token, transfer_with_memo,
+};
+
+// stands in for SPL Memo, which isn't part of the test validator
+fn memo_program(_program_id: &Pubkey, _accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    Ok(())
+}
+
+async fn prepare_with_balance() -> (ProgramTestContext, Pubkey, Keypair, Pubkey, Keypair) {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = program_test(&program_id);
+    program_test.add_program("spl_memo", MEMO_PROGRAM_ID, processor!(memo_program));
+    let (mut context, owner) = start(program_test).await;
+
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    let from = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    (context, program_id, owner, mint, from)
+}
+
+#[tokio::test]
+async fn test_transfer_with_memo() {
+    let (mut context, program_id, _owner, mint, from) = prepare_with_balance().await;
+    let to = Keypair::new();
+
+    transfer_with_memo(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        "invoice 42",
+        None,
+    )
+    .await
+    .unwrap();
+
+    let from_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &from.pubkey(),
+    )
+    .await;
+    let to_account = get_account(&mut context.banks_client, &program_id, &mint, &to.pubkey()).await;
+    assert_eq!(from_account.balance, sol(6.0));
+    assert_eq!(to_account.balance, sol(4.0));
+}
+
+#[tokio::test]
+async fn test_transfer_with_memo_logs_memo() {
+    let (mut context, program_id, _owner, mint, from) = prepare_with_balance().await;
+    let to = Keypair::new();
+
+    let fee_vault_pda =
+        find_transfer_fee_vault(&mut context.banks_client, &program_id, &mint).await;
+    let mut tx = Transaction::new_with_payer(
+        &[client::transfer_with_memo(
+            &program_id,
+            &from.pubkey(),
+            &mint,
+            &to.pubkey(),
+            sol(4.0),
+            String::from("invoice 42"),
+            &fee_vault_pda,
+            &[],
+        )],
+        Some(&from.pubkey()),
+    );
+    tx.sign(
+        &[&from],
+        context.banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    let simulation = context.banks_client.simulate_transaction(tx).await.unwrap();
+    let logs = simulation.simulation_details.unwrap().logs;
+
+    // the Memo event goes through sol_log_data, which the test runtime doesn't record
+    assert!(logs.contains(&String::from("Program log: Memo: invoice 42")));
+}
+
+#[tokio::test]
+async fn test_fail_transfer_with_memo_too_long() {
+    let (mut context, program_id, _owner, mint, from) = prepare_with_balance().await;
+
+    let result = transfer_with_memo(
+        &from,
+        &Keypair::new(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        &"a".repeat(MAX_MEMO_LENGTH + 1),
+        None,
+    )
+    .await;
+
+    assert_custom_error(result, SimpleTokenErrors::MemoTooLong);
+}
+
+#[tokio::test]
+async fn test_transfer_with_required_memo() {
+    let (mut context, program_id, owner, mint, from) = prepare_with_balance().await;
+    let to = Keypair::new();
+    set_memo_required(&owner, &program_id, &mint, &mut context.banks_client, true)
+        .await
+        .unwrap();
+
+    transfer_with_memo(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        "invoice 42",
+        Some("invoice 42"),
+    )
+    .await
+    .unwrap();
+
+    let to_account = get_account(&mut context.banks_client, &program_id, &mint, &to.pubkey()).await;
+    assert_eq!(to_account.balance, sol(4.0));
+}
+
+#[tokio::test]
+async fn test_fail_transfer_without_required_memo() {
+    let (mut context, program_id, owner, mint, from) = prepare_with_balance().await;
+    set_memo_required(&owner, &program_id, &mint, &mut context.banks_client, true)
+        .await
+        .unwrap();
+
+    let missing = transfer_with_memo(
+        &from,
+        &Keypair::new(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        "invoice 42",
+        None,
+    )
+    .await;
+    let mismatched = transfer_with_memo(
+        &from,
+        &Keypair::new(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        "invoice 42",
+        Some("invoice 43"),
+    )
+    .await;
+
+    assert_custom_error(missing, SimpleTokenErrors::MissingMemo);
+    assert_custom_error(mismatched, SimpleTokenErrors::MissingMemo);
+}
+
+#[tokio::test]
+async fn test_fail_plain_transfers_with_required_memo() {
+    let (mut context, program_id, owner, mint, from) = prepare_with_balance().await;
+    let delegate = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &delegate.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    approve(
+        &from,
+        &delegate,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+    )
+    .await
+    .unwrap();
+    set_memo_required(&owner, &program_id, &mint, &mut context.banks_client, true)
+        .await
+        .unwrap();
+
+    let to = Keypair::new();
+    let transfer = transfer_token(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+    )
+    .await;
+    let transfer_checked = transfer_checked_token(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        18,
+    )
+    .await;
+    let transfer_from = transfer_from_token(
+        &delegate,
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+    )
+    .await;
+    let batch = batch_transfer(
+        &from,
+        &[(to.pubkey(), sol(4.0))],
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await;
+
+    assert_custom_error(transfer, SimpleTokenErrors::MissingMemo);
+    assert_custom_error(transfer_checked, SimpleTokenErrors::MissingMemo);
+    assert_custom_error(transfer_from, SimpleTokenErrors::MissingMemo);
+    assert_custom_error(batch, SimpleTokenErrors::MissingMemo);
+    let from_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &from.pubkey(),
+    )
+    .await;
+    assert_eq!(from_account.balance, sol(10.0));
+}
+
+#[tokio::test]
+async fn test_fail_set_memo_required_not_admin() {
+    let (mut context, program_id, _owner, mint, from) = prepare_with_balance().await;
+
+    let result =
+        set_memo_required(&from, &program_id, &mint, &mut context.banks_client, true).await;
+
+    assert_custom_error(result, SimpleTokenErrors::InvalidOwner);
+}
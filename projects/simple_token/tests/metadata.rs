@@ -0,0 +1,99 @@
+use simple_token::{errors::SimpleTokenErrors, storage::Config};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, get_config, initialize, prepare, sol, transfer_sol, update_metadata,
+};
+
+#[tokio::test]
+async fn test_update_metadata() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    update_metadata(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        "Simple Reward Token",
+        "SRT",
+        "https://example.com/tokens/simple-reward-token/metadata.json",
+    )
+    .await
+    .unwrap();
+
+    let config_data: Config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config_data.name, "Simple Reward Token");
+    assert_eq!(config_data.symbol, "SRT");
+    assert_eq!(
+        config_data.uri,
+        "https://example.com/tokens/simple-reward-token/metadata.json"
+    );
+    assert_eq!(config_data.owner, owner.pubkey());
+
+    update_metadata(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        "S",
+        "S",
+        "",
+    )
+    .await
+    .unwrap();
+
+    let config_data: Config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config_data.name, "S");
+    assert_eq!(config_data.uri, "");
+}
+
+#[tokio::test]
+async fn test_fail_not_owner_update_metadata() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let not_owner = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &not_owner.pubkey(),
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = update_metadata(
+        &not_owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        "Fake Token",
+        "FAKE",
+        "",
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidOwner);
+}
+
+#[tokio::test]
+async fn test_fail_too_long_update_metadata() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let transaction_result = update_metadata(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        "Simple Token",
+        "A_SYMBOL_THAT_IS_WAY_TOO_LONG",
+        "",
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::MetadataTooLong);
+}
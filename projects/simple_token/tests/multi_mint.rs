@@ -0,0 +1,261 @@
+use borsh::BorshSerialize;
+use simple_token::{errors::SimpleTokenErrors, instructions as simple_token_instructions};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    system_program,
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, find_config_pda, find_transfer_fee_vault, find_user_pda, get_account,
+    get_config, initialize, initialize_mint, mint_to, prepare, process_transaction, sol,
+    transfer_sol, transfer_token,
+};
+
+#[tokio::test]
+async fn test_independent_mints() {
+    let (mut context, program_id, owner) = prepare().await;
+    let first_mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    let second_mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let to = Keypair::new();
+
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &first_mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &second_mint,
+        &mut context.banks_client,
+        sol(3.0),
+    )
+    .await
+    .unwrap();
+
+    transfer_token(
+        &from,
+        &to,
+        &program_id,
+        &first_mint,
+        &mut context.banks_client,
+        sol(4.0),
+    )
+    .await
+    .unwrap();
+
+    let first_from = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &first_mint,
+        &from.pubkey(),
+    )
+    .await;
+    let second_from = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &second_mint,
+        &from.pubkey(),
+    )
+    .await;
+    let first_to = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &first_mint,
+        &to.pubkey(),
+    )
+    .await;
+    let first_config = get_config(&mut context.banks_client, &program_id, &first_mint).await;
+    let second_config = get_config(&mut context.banks_client, &program_id, &second_mint).await;
+
+    assert_eq!(first_from.balance, sol(6.0));
+    assert_eq!(first_to.balance, sol(4.0));
+    assert_eq!(second_from.balance, sol(3.0));
+    assert_eq!(first_config.total_supply, sol(10.0));
+    assert_eq!(second_config.total_supply, sol(3.0));
+}
+
+#[tokio::test]
+async fn test_fail_not_owner_of_mint_mint() {
+    let (mut context, program_id, owner) = prepare().await;
+    let first_mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let other_owner = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &other_owner.pubkey(),
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    let second_mint = initialize(&other_owner, &program_id, &mut context.banks_client).await;
+
+    let to = Keypair::new();
+    mint_to(
+        &owner,
+        &to,
+        &program_id,
+        &first_mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = mint_to(
+        &owner,
+        &to,
+        &program_id,
+        &second_mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidOwner);
+}
+
+#[tokio::test]
+async fn test_fail_cross_mint_pda_transfer() {
+    let (mut context, program_id, owner) = prepare().await;
+    let first_mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    let second_mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let to = Keypair::new();
+
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &first_mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let from_pda = find_user_pda(&program_id, &first_mint, &from.pubkey());
+    let to_pda = find_user_pda(&program_id, &first_mint, &to.pubkey());
+
+    let fee_vault_pda =
+        find_transfer_fee_vault(&mut context.banks_client, &program_id, &second_mint).await;
+
+    let transfer_instruction = simple_token_instructions::Instruction::Transfer {
+        mint: second_mint,
+        to: to.pubkey(),
+        amount: sol(1.0),
+    };
+
+    let transaction_result = process_transaction(
+        &mut context.banks_client,
+        vec![Instruction::new_with_bytes(
+            program_id,
+            &transfer_instruction.try_to_vec().unwrap(),
+            vec![
+                AccountMeta::new(from.pubkey(), true),
+                AccountMeta::new(find_config_pda(&program_id, &second_mint), false),
+                AccountMeta::new(from_pda, false),
+                AccountMeta::new(to_pda, false),
+                AccountMeta::new(fee_vault_pda, false),
+                AccountMeta::new(system_program::id(), false),
+            ],
+        )],
+        vec![&from],
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidPda);
+}
+
+#[tokio::test]
+async fn test_fail_unsigned_mint_initialize() {
+    let (mut context, program_id, owner) = prepare().await;
+
+    let mint = Keypair::new();
+    let config_pda = find_config_pda(&program_id, &mint.pubkey());
+
+    let initialize_instruction = simple_token_instructions::Instruction::Initialize {
+        mint: mint.pubkey(),
+        owner: owner.pubkey(),
+        decimals: 18,
+        freeze_authority: None,
+        clawback_authority: None,
+        non_transferable: false,
+        max_supply: None,
+        name: String::from("Simple Token"),
+        symbol: String::from("SIMPLE"),
+        uri: String::from(""),
+    };
+
+    let transaction_result = process_transaction(
+        &mut context.banks_client,
+        vec![Instruction::new_with_bytes(
+            program_id,
+            &initialize_instruction.try_to_vec().unwrap(),
+            vec![
+                AccountMeta::new(owner.pubkey(), true),
+                AccountMeta::new_readonly(mint.pubkey(), false),
+                AccountMeta::new(config_pda, false),
+                AccountMeta::new(system_program::id(), false),
+            ],
+        )],
+        vec![&owner],
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidMint);
+}
+
+#[tokio::test]
+async fn test_fail_already_initialized_mint() {
+    let (mut context, program_id, owner) = prepare().await;
+
+    let mint = Keypair::new();
+    initialize_mint(&owner, &mint, &program_id, &mut context.banks_client, None)
+        .await
+        .unwrap();
+
+    let transaction_result = initialize_mint(
+        &owner,
+        &mint,
+        &program_id,
+        &mut context.banks_client,
+        Some(sol(1.0)),
+    )
+    .await;
+
+    assert!(transaction_result.is_err());
+    let config = get_config(&mut context.banks_client, &program_id, &mint.pubkey()).await;
+    assert_eq!(config.max_supply, None);
+}
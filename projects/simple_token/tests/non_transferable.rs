@@ -0,0 +1,163 @@
+use simple_token::errors::SimpleTokenErrors;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    approve, assert_custom_error, batch_transfer, burn_from, burn_own,
+    create_wrapped_token_account, get_account, get_config, initialize_non_transferable,
+    initialize_wrapped_mint, mint_to, prepare, sol, transfer_from_token, transfer_sol,
+    transfer_token, wrap,
+};
+
+async fn create_holder(
+    context: &mut ProgramTestContext,
+    owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+) -> Keypair {
+    let holder = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &holder.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    mint_to(
+        owner,
+        &holder,
+        program_id,
+        mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    holder
+}
+
+#[tokio::test]
+async fn test_non_transferable_mint_and_burn() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize_non_transferable(&owner, &program_id, &mut context.banks_client).await;
+    let holder = create_holder(&mut context, &owner, &program_id, &mint).await;
+
+    burn_from(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(3.0),
+    )
+    .await
+    .unwrap();
+    burn_own(
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(2.0),
+    )
+    .await
+    .unwrap();
+
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    let holder_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &holder.pubkey(),
+    )
+    .await;
+    assert!(config.non_transferable);
+    assert_eq!(config.total_supply, sol(5.0));
+    assert_eq!(holder_account.balance, sol(5.0));
+}
+
+#[tokio::test]
+async fn test_fail_non_transferable_transfer() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize_non_transferable(&owner, &program_id, &mut context.banks_client).await;
+    let holder = create_holder(&mut context, &owner, &program_id, &mint).await;
+    let to = Keypair::new();
+
+    let transfer_result = transfer_token(
+        &holder,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await;
+    let batch_transfer_result = batch_transfer(
+        &holder,
+        &[(to.pubkey(), sol(1.0))],
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await;
+
+    assert_custom_error(transfer_result, SimpleTokenErrors::NonTransferable);
+    assert_custom_error(batch_transfer_result, SimpleTokenErrors::NonTransferable);
+}
+
+#[tokio::test]
+async fn test_fail_non_transferable_transfer_from() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize_non_transferable(&owner, &program_id, &mut context.banks_client).await;
+    let holder = create_holder(&mut context, &owner, &program_id, &mint).await;
+    let delegate = create_holder(&mut context, &owner, &program_id, &mint).await;
+    approve(
+        &holder,
+        &delegate,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(5.0),
+    )
+    .await
+    .unwrap();
+
+    let result = transfer_from_token(
+        &delegate,
+        &holder,
+        &delegate,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await;
+
+    assert_custom_error(result, SimpleTokenErrors::NonTransferable);
+}
+
+#[tokio::test]
+async fn test_fail_non_transferable_wrap() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize_non_transferable(&owner, &program_id, &mut context.banks_client).await;
+    let holder = create_holder(&mut context, &owner, &program_id, &mint).await;
+    initialize_wrapped_mint(&owner, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    let token_account =
+        create_wrapped_token_account(&holder, &program_id, &mint, &mut context.banks_client).await;
+
+    let result = wrap(
+        &holder,
+        &token_account,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await;
+
+    assert_custom_error(result, SimpleTokenErrors::NonTransferable);
+}
@@ -0,0 +1,178 @@
+use simple_token::errors::SimpleTokenErrors;
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    accept_ownership, assert_custom_error, cancel_ownership_transfer, get_config, initialize,
+    mint_to, prepare, propose_owner, sol, transfer_sol,
+};
+
+#[tokio::test]
+async fn test_accept_ownership() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let new_owner = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &new_owner.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    propose_owner(
+        &owner,
+        &new_owner.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    // the current owner keeps its rights until the handover is accepted
+    mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    accept_ownership(&new_owner, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+
+    let transaction_result = mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(2.0),
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidOwner);
+
+    mint_to(
+        &new_owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_cancel_ownership_transfer() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let new_owner = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &new_owner.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    propose_owner(
+        &owner,
+        &new_owner.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+    cancel_ownership_transfer(&owner, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config.pending_owner, None);
+
+    let transaction_result =
+        accept_ownership(&new_owner, &program_id, &mint, &mut context.banks_client).await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidPendingOwner);
+}
+
+#[tokio::test]
+async fn test_fail_not_pending_owner_accept_ownership() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let new_owner = Keypair::new();
+    let not_new_owner = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &not_new_owner.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    propose_owner(
+        &owner,
+        &new_owner.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = accept_ownership(
+        &not_new_owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidPendingOwner);
+
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config.owner, owner.pubkey());
+}
+
+#[tokio::test]
+async fn test_fail_not_owner_cancel_ownership_transfer() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let new_owner = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &new_owner.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    propose_owner(
+        &owner,
+        &new_owner.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    let transaction_result =
+        cancel_ownership_transfer(&new_owner, &program_id, &mint, &mut context.banks_client).await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidOwner);
+}
@@ -0,0 +1,147 @@
+use simple_token::{errors::SimpleTokenErrors, storage::Role};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, burn_from, burn_own, get_account, get_config, grant_role, initialize,
+    mint_to, prepare, set_paused, sol, transfer_sol, transfer_token,
+};
+
+#[tokio::test]
+async fn test_pause() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let to = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    set_paused(&owner, &program_id, &mint, &mut context.banks_client, true)
+        .await
+        .unwrap();
+
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert!(config.paused);
+
+    let transaction_result = transfer_token(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::Paused);
+
+    let transaction_result = mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::Paused);
+
+    let transaction_result = burn_from(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::Paused);
+
+    let transaction_result = burn_own(
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::Paused);
+
+    set_paused(&owner, &program_id, &mint, &mut context.banks_client, false)
+        .await
+        .unwrap();
+
+    transfer_token(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(2.0),
+    )
+    .await
+    .unwrap();
+
+    let to_account = get_account(&mut context.banks_client, &program_id, &mint, &to.pubkey()).await;
+    assert_eq!(to_account.balance, sol(2.0));
+}
+
+#[tokio::test]
+async fn test_pauser_role() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let pauser = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &pauser.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result =
+        set_paused(&pauser, &program_id, &mint, &mut context.banks_client, true).await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidOwner);
+
+    grant_role(
+        &owner,
+        Role::Pauser,
+        &pauser.pubkey(),
+        None,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    // the retry has to land in a later slot, otherwise it gets the result of the rejected attempt
+    let slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(slot + 1).unwrap();
+    set_paused(&pauser, &program_id, &mint, &mut context.banks_client, true)
+        .await
+        .unwrap();
+
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert!(config.paused);
+}
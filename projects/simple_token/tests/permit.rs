@@ -0,0 +1,301 @@
+use simple_token::{client, errors::SimpleTokenErrors};
+use solana_program::clock::Clock;
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, burn_own, close_account, get_account, get_allowance, get_permit_nonce,
+    initialize, mint_to, permit, prepare, process_transaction, revoke, sol, transfer_from_token,
+    transfer_sol,
+};
+
+#[tokio::test]
+async fn test_permit() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    // neither the holder nor the delegate need any SOL, the relayer pays for everything
+    let holder = Keypair::new();
+    let delegate = Keypair::new();
+    let relayer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    mint_to(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    permit(
+        &relayer,
+        &holder,
+        &holder.pubkey(),
+        &owner.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        0,
+        i64::MAX,
+    )
+    .await
+    .unwrap();
+
+    let allowance = get_allowance(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &holder.pubkey(),
+        &owner.pubkey(),
+    )
+    .await;
+    let permit_nonce = get_permit_nonce(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &holder.pubkey(),
+    )
+    .await;
+    assert_eq!(allowance.amount, sol(4.0));
+    assert_eq!(permit_nonce, 1);
+
+    transfer_from_token(
+        &owner,
+        &holder,
+        &delegate,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+    )
+    .await
+    .unwrap();
+
+    let delegate_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &delegate.pubkey(),
+    )
+    .await;
+    assert_eq!(delegate_account.balance, sol(4.0));
+}
+
+#[tokio::test]
+async fn test_fail_replayed_permit() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    let relayer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    permit(
+        &relayer,
+        &holder,
+        &holder.pubkey(),
+        &owner.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        0,
+        i64::MAX,
+    )
+    .await
+    .unwrap();
+
+    // a different relayer keeps the transaction from being deduplicated
+    let transaction_result = permit(
+        &owner,
+        &holder,
+        &holder.pubkey(),
+        &owner.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        0,
+        i64::MAX,
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidPermitNonce);
+}
+
+#[tokio::test]
+async fn test_fail_replayed_permit_after_close() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    let delegate = Keypair::new();
+    let relayer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &holder.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    mint_to(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    permit(
+        &relayer,
+        &holder,
+        &holder.pubkey(),
+        &delegate.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        0,
+        i64::MAX,
+    )
+    .await
+    .unwrap();
+    revoke(
+        &holder,
+        &delegate,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    // closing and recreating the token account doesn't reset the nonce
+    burn_own(
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    close_account(
+        &holder,
+        &holder.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+    mint_to(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(2.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = permit(
+        &owner,
+        &holder,
+        &holder.pubkey(),
+        &delegate.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        0,
+        i64::MAX,
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidPermitNonce);
+}
+
+#[tokio::test]
+async fn test_fail_expired_permit() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let holder = Keypair::new();
+    let transaction_result = permit(
+        &owner,
+        &holder,
+        &holder.pubkey(),
+        &owner.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        0,
+        clock.unix_timestamp - 1,
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::PermitExpired);
+}
+
+#[tokio::test]
+async fn test_fail_not_holder_signed_permit() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    let transaction_result = permit(
+        &owner,
+        &owner,
+        &holder.pubkey(),
+        &owner.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        0,
+        i64::MAX,
+    )
+    .await;
+
+    assert_custom_error(
+        transaction_result,
+        SimpleTokenErrors::InvalidPermitSignature,
+    );
+}
+
+#[tokio::test]
+async fn test_fail_missing_signature_permit() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    let transaction_result = process_transaction(
+        &mut context.banks_client,
+        vec![client::permit(
+            &program_id,
+            &owner.pubkey(),
+            &mint,
+            &holder.pubkey(),
+            &owner.pubkey(),
+            sol(4.0),
+            0,
+            i64::MAX,
+        )],
+        vec![&owner],
+    )
+    .await;
+
+    assert_custom_error(
+        transaction_result,
+        SimpleTokenErrors::InvalidPermitSignature,
+    );
+}
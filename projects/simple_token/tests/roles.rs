@@ -0,0 +1,283 @@
+use simple_token::{errors::SimpleTokenErrors, storage::Role};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, burn_from, get_account, get_config, grant_role, initialize, mint_to,
+    prepare, propose_owner, revoke_role, sol, transfer_sol,
+};
+
+#[tokio::test]
+async fn test_minter_role() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let minter = Keypair::new();
+    let to = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &minter.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    grant_role(
+        &owner,
+        Role::Minter,
+        &minter.pubkey(),
+        None,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &minter,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let to_account = get_account(&mut context.banks_client, &program_id, &mint, &to.pubkey()).await;
+    assert_eq!(to_account.balance, sol(10.0));
+
+    // minting doesn't give any say over the ownership
+    let transaction_result = propose_owner(
+        &minter,
+        &minter.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidOwner);
+
+    revoke_role(
+        &owner,
+        Role::Minter,
+        &minter.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = mint_to(
+        &minter,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidOwner);
+
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert!(config.roles.is_empty());
+}
+
+#[tokio::test]
+async fn test_fail_mint_quota_exceeded() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let minter = Keypair::new();
+    let to = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &minter.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    grant_role(
+        &owner,
+        Role::Minter,
+        &minter.pubkey(),
+        Some(sol(5.0)),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &minter,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(2.5),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = mint_to(
+        &minter,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(3.0),
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::MintQuotaExceeded);
+
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config.roles[0].minted, sol(2.5));
+
+    // the owner isn't bound by any quota
+    mint_to(
+        &owner,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(3.0),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_burner_role() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let burner = Keypair::new();
+    let from = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &burner.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = burn_from(
+        &burner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(2.0),
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidOwner);
+
+    grant_role(
+        &owner,
+        Role::Burner,
+        &burner.pubkey(),
+        None,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    burn_from(
+        &burner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    let from_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &from.pubkey(),
+    )
+    .await;
+    assert_eq!(from_account.balance, sol(9.0));
+}
+
+#[tokio::test]
+async fn test_admin_role() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let admin = Keypair::new();
+    let minter = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &admin.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    grant_role(
+        &owner,
+        Role::Admin,
+        &admin.pubkey(),
+        None,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    grant_role(
+        &admin,
+        Role::Minter,
+        &minter.pubkey(),
+        Some(sol(1.0)),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert!(config.has_role(Role::Minter, &minter.pubkey()));
+    assert_eq!(config.roles[1].mint_quota, Some(sol(1.0)));
+
+    // only the owner manages admins
+    let transaction_result = grant_role(
+        &admin,
+        Role::Admin,
+        &minter.pubkey(),
+        None,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidOwner);
+}
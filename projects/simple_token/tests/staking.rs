@@ -0,0 +1,395 @@
+use simple_token::{errors::SimpleTokenErrors, events::Event};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, capture_events, claim_rewards, emitted, find_stake_pool_pda,
+    fund_staking_rewards, get_account, get_stake_account, get_stake_pool, initialize, mint_to,
+    prepare, sol, stake, transfer_sol, unstake, warp_to_timestamp,
+};
+
+async fn create_staker(
+    context: &mut ProgramTestContext,
+    owner: &Keypair,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Keypair {
+    let staker = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &staker.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    mint_to(
+        owner,
+        &staker,
+        program_id,
+        mint,
+        &mut context.banks_client,
+        amount,
+    )
+    .await
+    .unwrap();
+    staker
+}
+
+async fn balance(
+    context: &mut ProgramTestContext,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    user: &Keypair,
+) -> u64 {
+    get_account(&mut context.banks_client, program_id, mint, &user.pubkey())
+        .await
+        .balance
+}
+
+#[tokio::test]
+async fn test_staking_rewards() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(100.0),
+    )
+    .await
+    .unwrap();
+    let alice = create_staker(&mut context, &owner, &program_id, &mint, sol(10.0)).await;
+    let bob = create_staker(&mut context, &owner, &program_id, &mint, sol(30.0)).await;
+
+    warp_to_timestamp(&mut context, 1_000).await;
+    fund_staking_rewards(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+        sol(0.01),
+    )
+    .await
+    .unwrap();
+    stake(
+        &alice,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        balance(&mut context, &program_id, &mint, &owner).await,
+        sol(90.0)
+    );
+    assert_eq!(balance(&mut context, &program_id, &mint, &alice).await, 0);
+
+    // alice earns everything until bob joins, then a quarter of it
+    warp_to_timestamp(&mut context, 1_100).await;
+    stake(
+        &bob,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(30.0),
+    )
+    .await
+    .unwrap();
+
+    warp_to_timestamp(&mut context, 1_500).await;
+    claim_rewards(&alice, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    claim_rewards(&bob, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    assert_eq!(
+        balance(&mut context, &program_id, &mint, &alice).await,
+        sol(2.0)
+    );
+    assert_eq!(
+        balance(&mut context, &program_id, &mint, &bob).await,
+        sol(3.0)
+    );
+
+    unstake(
+        &alice,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    let pool = get_stake_pool(&mut context.banks_client, &program_id, &mint).await;
+    let alice_stake = get_stake_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &alice.pubkey(),
+    )
+    .await;
+    assert_eq!(
+        balance(&mut context, &program_id, &mint, &alice).await,
+        sol(12.0)
+    );
+    assert_eq!(alice_stake.amount, 0);
+    assert_eq!(alice_stake.pending_rewards, 0);
+    assert_eq!(pool.total_staked, sol(30.0));
+    assert_eq!(pool.reward_reserve, sol(5.0));
+}
+
+#[tokio::test]
+async fn test_staking_rewards_capped_by_reserve() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    let staker = create_staker(&mut context, &owner, &program_id, &mint, sol(10.0)).await;
+
+    warp_to_timestamp(&mut context, 1_000).await;
+    fund_staking_rewards(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+        sol(0.01),
+    )
+    .await
+    .unwrap();
+    stake(
+        &staker,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    warp_to_timestamp(&mut context, 3_000).await;
+    claim_rewards(&staker, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+
+    let pool = get_stake_pool(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(
+        balance(&mut context, &program_id, &mint, &staker).await,
+        sol(1.0)
+    );
+    assert_eq!(pool.reward_reserve, 0);
+}
+
+#[tokio::test]
+async fn test_staking_rewards_rounded_down_stay_in_reserve() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    let staker = create_staker(&mut context, &owner, &program_id, &mint, sol(10_000_000.0)).await;
+
+    warp_to_timestamp(&mut context, 1_000).await;
+    fund_staking_rewards(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+        1,
+    )
+    .await
+    .unwrap();
+    stake(
+        &staker,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10_000_000.0),
+    )
+    .await
+    .unwrap();
+
+    // 500 tokens spread over the whole stake round down to nothing per staked token
+    warp_to_timestamp(&mut context, 1_500).await;
+    claim_rewards(&staker, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    let pool = get_stake_pool(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(balance(&mut context, &program_id, &mint, &staker).await, 0);
+    assert_eq!(pool.reward_reserve, sol(1.0));
+
+    // 28_500 tokens only pay out 20_000, the remainder isn't lost from the reserve
+    warp_to_timestamp(&mut context, 30_000).await;
+    claim_rewards(&staker, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    let pool = get_stake_pool(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(
+        balance(&mut context, &program_id, &mint, &staker).await,
+        20_000
+    );
+    assert_eq!(pool.reward_reserve, sol(1.0) - 20_000);
+}
+
+#[tokio::test]
+async fn test_staking_emits_events() {
+    let (mut context, program_id, owner) = prepare().await;
+    capture_events();
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    let staker = create_staker(&mut context, &owner, &program_id, &mint, sol(10.0)).await;
+
+    warp_to_timestamp(&mut context, 1_000).await;
+    fund_staking_rewards(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+        sol(0.01),
+    )
+    .await
+    .unwrap();
+    stake(
+        &staker,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    warp_to_timestamp(&mut context, 3_000).await;
+    claim_rewards(&staker, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    unstake(
+        &staker,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let stake_pool = find_stake_pool_pda(&program_id, &mint);
+    let transfer = |from: Pubkey, to: Pubkey, amount: u64| Event::Transfer {
+        mint,
+        from,
+        to,
+        amount,
+        fee: 0,
+    };
+    let events = emitted(&mint);
+    assert_eq!(
+        events[events.len() - 4..],
+        [
+            transfer(owner.pubkey(), stake_pool, sol(1.0)),
+            transfer(staker.pubkey(), stake_pool, sol(10.0)),
+            transfer(stake_pool, staker.pubkey(), sol(1.0)),
+            transfer(stake_pool, staker.pubkey(), sol(10.0)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_fail_unstake_more_than_staked() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    let staker = create_staker(&mut context, &owner, &program_id, &mint, sol(10.0)).await;
+    fund_staking_rewards(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+        sol(0.01),
+    )
+    .await
+    .unwrap();
+    stake(
+        &staker,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(5.0),
+    )
+    .await
+    .unwrap();
+
+    let result = unstake(
+        &staker,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(6.0),
+    )
+    .await;
+
+    assert_custom_error(result, SimpleTokenErrors::InsufficientStake);
+}
+
+#[tokio::test]
+async fn test_fail_fund_staking_rewards_not_owner() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    let staker = create_staker(&mut context, &owner, &program_id, &mint, sol(10.0)).await;
+
+    let result = fund_staking_rewards(
+        &staker,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+        sol(0.01),
+    )
+    .await;
+
+    assert_custom_error(result, SimpleTokenErrors::InvalidOwner);
+}
@@ -0,0 +1,162 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use simple_token::{
+    errors::SimpleTokenErrors, instructions as simple_token_instructions, instructions::Supply,
+    storage::Config,
+};
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program_test::tokio;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, burn_from, find_config_pda, get_account, get_config, initialize,
+    initialize_with_max_supply, mint_to, prepare, sol,
+};
+
+#[tokio::test]
+async fn test_total_supply() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let first = Keypair::new();
+    let second = Keypair::new();
+
+    mint_to(
+        &owner,
+        &first,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    mint_to(
+        &owner,
+        &second,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(5.0),
+    )
+    .await
+    .unwrap();
+    burn_from(
+        &owner,
+        &first,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(3.0),
+    )
+    .await
+    .unwrap();
+
+    let config_data: Config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    let first_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &first.pubkey(),
+    )
+    .await;
+    let second_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &second.pubkey(),
+    )
+    .await;
+    assert_eq!(config_data.total_supply, sol(12.0));
+    assert_eq!(
+        config_data.total_supply,
+        first_account.balance + second_account.balance
+    );
+}
+
+#[tokio::test]
+async fn test_fail_max_supply_exceeded_mint() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize_with_max_supply(
+        &owner,
+        &program_id,
+        &mut context.banks_client,
+        Some(sol(10.0)),
+    )
+    .await;
+
+    let to = Keypair::new();
+
+    mint_to(
+        &owner,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = mint_to(
+        &owner,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        1,
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::MaxSupplyExceeded);
+}
+
+#[tokio::test]
+async fn test_get_supply() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize_with_max_supply(
+        &owner,
+        &program_id,
+        &mut context.banks_client,
+        Some(sol(100.0)),
+    )
+    .await;
+
+    let to = Keypair::new();
+    mint_to(
+        &owner,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let config_pda = find_config_pda(&program_id, &mint);
+    let get_supply_instruction = simple_token_instructions::Instruction::GetSupply { mint };
+
+    let mut tx = Transaction::new_with_payer(
+        &[Instruction::new_with_bytes(
+            program_id,
+            &get_supply_instruction.try_to_vec().unwrap(),
+            vec![AccountMeta::new_readonly(config_pda, false)],
+        )],
+        Some(&owner.pubkey()),
+    );
+    tx.sign(
+        &[&owner],
+        context.banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    let simulation = context.banks_client.simulate_transaction(tx).await.unwrap();
+    let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+
+    let supply = Supply::try_from_slice(&return_data.data).unwrap();
+    assert_eq!(return_data.program_id, program_id);
+    assert_eq!(supply.total_supply, sol(10.0));
+    assert_eq!(supply.max_supply, Some(sol(100.0)));
+}
@@ -0,0 +1,154 @@
+use simple_token::{
+    amount::{amount_to_ui_amount, ui_amount_to_amount},
+    errors::SimpleTokenErrors,
+};
+use solana_program::program_error::ProgramError;
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, get_account, initialize, mint_to, prepare, sol, transfer_checked_token,
+    transfer_sol,
+};
+
+#[tokio::test]
+async fn test_transfer_checked() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let to = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    transfer_checked_token(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        18,
+    )
+    .await
+    .unwrap();
+
+    let from_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &from.pubkey(),
+    )
+    .await;
+    let to_account = get_account(&mut context.banks_client, &program_id, &mint, &to.pubkey()).await;
+    assert_eq!(from_account.balance, sol(6.0));
+    assert_eq!(to_account.balance, sol(4.0));
+}
+
+#[tokio::test]
+async fn test_fail_transfer_checked_wrong_decimals() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let to = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let result = transfer_checked_token(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        9,
+    )
+    .await;
+
+    assert_custom_error(result, SimpleTokenErrors::InvalidDecimals);
+}
+
+#[test]
+fn test_ui_amount_conversion() {
+    assert_eq!(amount_to_ui_amount(150, 2), "1.5");
+    assert_eq!(amount_to_ui_amount(1, 18), "0.000000000000000001");
+    assert_eq!(amount_to_ui_amount(0, 9), "0");
+    assert_eq!(amount_to_ui_amount(42, 0), "42");
+    assert_eq!(amount_to_ui_amount(2_000_000_000, 9), "2");
+
+    assert_eq!(ui_amount_to_amount("1.5", 2), Ok(150));
+    assert_eq!(ui_amount_to_amount("0.000000000000000001", 18), Ok(1));
+    assert_eq!(ui_amount_to_amount(".5", 1), Ok(5));
+    assert_eq!(ui_amount_to_amount("0", 9), Ok(0));
+    assert_eq!(ui_amount_to_amount("18446744073709551615", 0), Ok(u64::MAX));
+    assert_eq!(ui_amount_to_amount("1.50", 1), Ok(15));
+    assert_eq!(ui_amount_to_amount("1.0", 0), Ok(1));
+    assert_eq!(ui_amount_to_amount("0.000", 2), Ok(0));
+
+    for amount in [0, 1, 150, 123_456_789, u64::MAX] {
+        let ui_amount = amount_to_ui_amount(amount, 9);
+        assert_eq!(ui_amount_to_amount(&ui_amount, 9), Ok(amount));
+    }
+}
+
+#[test]
+fn test_fail_ui_amount_conversion() {
+    assert_eq!(
+        ui_amount_to_amount("1.234", 2),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        ui_amount_to_amount("", 2),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        ui_amount_to_amount(".", 2),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        ui_amount_to_amount("-1", 2),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        ui_amount_to_amount("1e3", 2),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        ui_amount_to_amount("18446744073709551616", 0),
+        Err(ProgramError::ArithmeticOverflow)
+    );
+}
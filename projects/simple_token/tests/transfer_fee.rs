@@ -0,0 +1,322 @@
+use borsh::BorshSerialize;
+use simple_token::{
+    errors::SimpleTokenErrors, instructions as simple_token_instructions, storage::TransferFee,
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    system_program,
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    approve, assert_custom_error, find_config_pda, find_fee_vault_pda, find_user_pda, get_account,
+    get_config, initialize, mint_to, prepare, process_transaction, set_transfer_fee, sol,
+    transfer_from_token, transfer_sol, transfer_token, withdraw_fees,
+};
+
+#[tokio::test]
+async fn test_set_transfer_fee() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let transfer_fee = TransferFee {
+        basis_points: 100,
+        maximum_fee: sol(1.0),
+        fee_collector: Keypair::new().pubkey(),
+    };
+    set_transfer_fee(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        Some(transfer_fee.clone()),
+    )
+    .await
+    .unwrap();
+
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config.transfer_fee, Some(transfer_fee));
+    assert_eq!(config.name, "Simple Token");
+
+    set_transfer_fee(&owner, &program_id, &mint, &mut context.banks_client, None)
+        .await
+        .unwrap();
+
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config.transfer_fee, None);
+}
+
+#[tokio::test]
+async fn test_transfer_with_fee() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let to = Keypair::new();
+    let fee_collector = Keypair::new();
+    for user in [&from, &fee_collector] {
+        transfer_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &user.pubkey(),
+            sol(1.0),
+        )
+        .await
+        .unwrap();
+    }
+
+    set_transfer_fee(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        Some(TransferFee {
+            basis_points: 100,
+            maximum_fee: sol(1.0),
+            fee_collector: fee_collector.pubkey(),
+        }),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    transfer_token(
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let to_account = get_account(&mut context.banks_client, &program_id, &mint, &to.pubkey()).await;
+    assert_eq!(to_account.balance, sol(9.9));
+
+    withdraw_fees(
+        &fee_collector,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+    )
+    .await
+    .unwrap();
+
+    let fee_collector_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &fee_collector.pubkey(),
+    )
+    .await;
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(fee_collector_account.balance, sol(0.1));
+    assert_eq!(config.total_supply, sol(10.0));
+}
+
+#[tokio::test]
+async fn test_transfer_from_with_maximum_fee() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let delegate = Keypair::new();
+    let to = Keypair::new();
+    let fee_collector = Keypair::new();
+    for user in [&from, &delegate] {
+        transfer_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &user.pubkey(),
+            sol(1.0),
+        )
+        .await
+        .unwrap();
+    }
+
+    set_transfer_fee(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        Some(TransferFee {
+            basis_points: 5_000,
+            maximum_fee: sol(1.0),
+            fee_collector: fee_collector.pubkey(),
+        }),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    approve(
+        &from,
+        &delegate,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    transfer_from_token(
+        &delegate,
+        &from,
+        &to,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let to_account = get_account(&mut context.banks_client, &program_id, &mint, &to.pubkey()).await;
+    assert_eq!(to_account.balance, sol(9.0));
+}
+
+#[tokio::test]
+async fn test_fail_not_owner_set_transfer_fee() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let not_owner = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &not_owner.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = set_transfer_fee(
+        &not_owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        Some(TransferFee {
+            basis_points: 100,
+            maximum_fee: sol(1.0),
+            fee_collector: not_owner.pubkey(),
+        }),
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidOwner);
+}
+
+#[tokio::test]
+async fn test_fail_too_high_set_transfer_fee() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let transaction_result = set_transfer_fee(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        Some(TransferFee {
+            basis_points: 10_001,
+            maximum_fee: sol(1.0),
+            fee_collector: owner.pubkey(),
+        }),
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidTransferFee);
+}
+
+#[tokio::test]
+async fn test_fail_invalid_fee_vault_transfer() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let from = Keypair::new();
+    let to = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &from.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    set_transfer_fee(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        Some(TransferFee {
+            basis_points: 100,
+            maximum_fee: sol(1.0),
+            fee_collector: owner.pubkey(),
+        }),
+    )
+    .await
+    .unwrap();
+
+    mint_to(
+        &owner,
+        &from,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    let transfer_instruction = simple_token_instructions::Instruction::Transfer {
+        mint,
+        to: to.pubkey(),
+        amount: sol(1.0),
+    };
+
+    let transaction_result = process_transaction(
+        &mut context.banks_client,
+        vec![Instruction::new_with_bytes(
+            program_id,
+            &transfer_instruction.try_to_vec().unwrap(),
+            vec![
+                AccountMeta::new(from.pubkey(), true),
+                AccountMeta::new(find_config_pda(&program_id, &mint), false),
+                AccountMeta::new(find_user_pda(&program_id, &mint, &from.pubkey()), false),
+                AccountMeta::new(find_user_pda(&program_id, &mint, &to.pubkey()), false),
+                AccountMeta::new(
+                    find_fee_vault_pda(&program_id, &mint, &from.pubkey()),
+                    false,
+                ),
+                AccountMeta::new(system_program::id(), false),
+            ],
+        )],
+        vec![&from],
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidPda);
+}
@@ -0,0 +1,398 @@
+use borsh::BorshDeserialize;
+use simple_token::{client, errors::SimpleTokenErrors, transfer_hook::TransferHookExecute};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, InstructionError},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use solana_program_test::{processor, tokio, BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signature, Signer},
+    transaction::TransactionError,
+};
+
+mod helpers;
+use crate::helpers::{
+    approve, assert_custom_error, create_wrapped_token_account, find_transfer_fee_vault,
+    get_account, initialize, initialize_wrapped_mint, mint_to, process_transaction, program_test,
+    set_transfer_hook, sol, start, transfer_sol, wrap,
+};
+
+const RECIPIENT_NOT_ALLOWED: u32 = 1000;
+
+// a compliance program which only lets tokens go to the recipients listed in its allowlist account
+fn allowlist_hook(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let execute = TransferHookExecute::try_from_slice(data)?;
+    let allowlist = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let allowed = allowlist
+        .try_borrow_data()?
+        .chunks(32)
+        .any(|recipient| recipient == execute.to.as_ref());
+    if !allowed {
+        msg!("Recipient {} not allowed", execute.to);
+        return Err(ProgramError::Custom(RECIPIENT_NOT_ALLOWED));
+    }
+    Ok(())
+}
+
+struct HookSetup {
+    context: ProgramTestContext,
+    program_id: Pubkey,
+    owner: Keypair,
+    mint: Pubkey,
+    hook_program_id: Pubkey,
+    allowlist: Pubkey,
+    allowed: Keypair,
+}
+
+async fn prepare_with_hook() -> HookSetup {
+    let program_id = Pubkey::new_unique();
+    let hook_program_id = Pubkey::new_unique();
+    let allowlist = Pubkey::new_unique();
+    let allowed = Keypair::new();
+
+    let mut program_test = program_test(&program_id);
+    program_test.add_program(
+        "allowlist_hook",
+        hook_program_id,
+        processor!(allowlist_hook),
+    );
+    program_test.add_account(
+        allowlist,
+        Account {
+            lamports: sol(1.0),
+            data: allowed.pubkey().to_bytes().to_vec(),
+            owner: hook_program_id,
+            ..Account::default()
+        },
+    );
+    let (mut context, owner) = start(program_test).await;
+
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+    mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    set_transfer_hook(
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        Some(hook_program_id),
+    )
+    .await
+    .unwrap();
+
+    HookSetup {
+        context,
+        program_id,
+        owner,
+        mint,
+        hook_program_id,
+        allowlist,
+        allowed,
+    }
+}
+
+async fn transfer_with_hook(
+    setup: &mut HookSetup,
+    to: &Pubkey,
+    amount: u64,
+    hook_accounts: &[AccountMeta],
+) -> Result<Signature, BanksClientError> {
+    let fee_vault_pda = find_transfer_fee_vault(
+        &mut setup.context.banks_client,
+        &setup.program_id,
+        &setup.mint,
+    )
+    .await;
+
+    process_transaction(
+        &mut setup.context.banks_client,
+        vec![client::transfer(
+            &setup.program_id,
+            &setup.owner.pubkey(),
+            &setup.mint,
+            to,
+            amount,
+            &fee_vault_pda,
+            hook_accounts,
+        )],
+        vec![&setup.owner],
+    )
+    .await
+}
+
+fn assert_hook_error(transaction_result: Result<Signature, BanksClientError>) {
+    match transaction_result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(error_code),
+        ))) => assert_eq!(error_code, RECIPIENT_NOT_ALLOWED),
+        _ => panic!("Should fail"),
+    }
+}
+
+#[tokio::test]
+async fn test_transfer_hook() {
+    let mut setup = prepare_with_hook().await;
+
+    let hook_accounts = vec![
+        AccountMeta::new_readonly(setup.hook_program_id, false),
+        AccountMeta::new_readonly(setup.allowlist, false),
+    ];
+    let allowed = setup.allowed.pubkey();
+    transfer_with_hook(&mut setup, &allowed, sol(4.0), &hook_accounts)
+        .await
+        .unwrap();
+
+    let allowed_account = get_account(
+        &mut setup.context.banks_client,
+        &setup.program_id,
+        &setup.mint,
+        &allowed,
+    )
+    .await;
+    assert_eq!(allowed_account.balance, sol(4.0));
+}
+
+#[tokio::test]
+async fn test_fail_vetoed_transfer_hook() {
+    let mut setup = prepare_with_hook().await;
+
+    let hook_accounts = vec![
+        AccountMeta::new_readonly(setup.hook_program_id, false),
+        AccountMeta::new_readonly(setup.allowlist, false),
+    ];
+    let transaction_result = transfer_with_hook(
+        &mut setup,
+        &Keypair::new().pubkey(),
+        sol(4.0),
+        &hook_accounts,
+    )
+    .await;
+
+    assert_hook_error(transaction_result);
+}
+
+#[tokio::test]
+async fn test_fail_missing_program_transfer_hook() {
+    let mut setup = prepare_with_hook().await;
+
+    let allowed = setup.allowed.pubkey();
+    let transaction_result = transfer_with_hook(&mut setup, &allowed, sol(4.0), &[]).await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidTransferHook);
+}
+
+#[tokio::test]
+async fn test_fail_vetoed_transfer_from_hook() {
+    let mut setup = prepare_with_hook().await;
+
+    let delegate = Keypair::new();
+    transfer_sol(
+        &mut setup.context.banks_client,
+        &setup.context.payer,
+        &delegate.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    approve(
+        &setup.owner,
+        &delegate,
+        &setup.program_id,
+        &setup.mint,
+        &mut setup.context.banks_client,
+        sol(4.0),
+    )
+    .await
+    .unwrap();
+
+    // going through an allowance must not skip the hook
+    let fee_vault_pda = find_transfer_fee_vault(
+        &mut setup.context.banks_client,
+        &setup.program_id,
+        &setup.mint,
+    )
+    .await;
+    let transaction_result = process_transaction(
+        &mut setup.context.banks_client,
+        vec![client::transfer_from(
+            &setup.program_id,
+            &delegate.pubkey(),
+            &setup.mint,
+            &setup.owner.pubkey(),
+            &Keypair::new().pubkey(),
+            sol(4.0),
+            &fee_vault_pda,
+            &[
+                AccountMeta::new_readonly(setup.hook_program_id, false),
+                AccountMeta::new_readonly(setup.allowlist, false),
+            ],
+        )],
+        vec![&delegate],
+    )
+    .await;
+
+    assert_hook_error(transaction_result);
+}
+
+// wraps owner tokens and returns the owner's wrapped token account
+async fn wrap_with_hook(setup: &mut HookSetup, amount: u64) -> Pubkey {
+    initialize_wrapped_mint(
+        &setup.owner,
+        &setup.program_id,
+        &setup.mint,
+        &mut setup.context.banks_client,
+    )
+    .await
+    .unwrap();
+    let token_account = create_wrapped_token_account(
+        &setup.owner,
+        &setup.program_id,
+        &setup.mint,
+        &mut setup.context.banks_client,
+    )
+    .await;
+    wrap(
+        &setup.owner,
+        &token_account,
+        &setup.program_id,
+        &setup.mint,
+        &mut setup.context.banks_client,
+        amount,
+    )
+    .await
+    .unwrap();
+
+    token_account
+}
+
+#[tokio::test]
+async fn test_unwrap_transfer_hook() {
+    let mut setup = prepare_with_hook().await;
+    let owner_token_account = wrap_with_hook(&mut setup, sol(4.0)).await;
+
+    // the wrapped tokens change hands through the token program, the hook vets whoever unwraps them
+    transfer_sol(
+        &mut setup.context.banks_client,
+        &setup.context.payer,
+        &setup.allowed.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    let allowed_token_account = create_wrapped_token_account(
+        &setup.allowed,
+        &setup.program_id,
+        &setup.mint,
+        &mut setup.context.banks_client,
+    )
+    .await;
+    process_transaction(
+        &mut setup.context.banks_client,
+        vec![spl_token::instruction::transfer(
+            &spl_token::id(),
+            &owner_token_account,
+            &allowed_token_account,
+            &setup.owner.pubkey(),
+            &[],
+            sol(4.0),
+        )
+        .unwrap()],
+        vec![&setup.owner],
+    )
+    .await
+    .unwrap();
+
+    process_transaction(
+        &mut setup.context.banks_client,
+        vec![client::unwrap(
+            &setup.program_id,
+            &setup.allowed.pubkey(),
+            &setup.mint,
+            &allowed_token_account,
+            sol(4.0),
+            &[
+                AccountMeta::new_readonly(setup.hook_program_id, false),
+                AccountMeta::new_readonly(setup.allowlist, false),
+            ],
+        )],
+        vec![&setup.allowed],
+    )
+    .await
+    .unwrap();
+
+    let allowed = setup.allowed.pubkey();
+    let allowed_account = get_account(
+        &mut setup.context.banks_client,
+        &setup.program_id,
+        &setup.mint,
+        &allowed,
+    )
+    .await;
+    assert_eq!(allowed_account.balance, sol(4.0));
+}
+
+#[tokio::test]
+async fn test_fail_vetoed_unwrap_transfer_hook() {
+    let mut setup = prepare_with_hook().await;
+    let token_account = wrap_with_hook(&mut setup, sol(4.0)).await;
+
+    // the owner is not on the allowlist, so unwrapping must not hand the tokens back
+    let transaction_result = process_transaction(
+        &mut setup.context.banks_client,
+        vec![client::unwrap(
+            &setup.program_id,
+            &setup.owner.pubkey(),
+            &setup.mint,
+            &token_account,
+            sol(4.0),
+            &[
+                AccountMeta::new_readonly(setup.hook_program_id, false),
+                AccountMeta::new_readonly(setup.allowlist, false),
+            ],
+        )],
+        vec![&setup.owner],
+    )
+    .await;
+
+    assert_hook_error(transaction_result);
+}
+
+#[tokio::test]
+async fn test_fail_not_admin_set_transfer_hook() {
+    let mut setup = prepare_with_hook().await;
+
+    let not_admin = Keypair::new();
+    transfer_sol(
+        &mut setup.context.banks_client,
+        &setup.context.payer,
+        &not_admin.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = set_transfer_hook(
+        &not_admin,
+        &setup.program_id,
+        &setup.mint,
+        &mut setup.context.banks_client,
+        None,
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidOwner);
+}
@@ -0,0 +1,298 @@
+use simple_token::{errors::SimpleTokenErrors, events::Event};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, capture_events, claim_vested, emitted, find_user_pda, find_vesting_pda,
+    get_account, get_config, get_vesting_schedule, initialize, mint_vested, prepare, set_paused,
+    sol, transfer_sol, warp_to_timestamp,
+};
+
+#[tokio::test]
+async fn test_vesting() {
+    let (mut context, program_id, owner) = prepare().await;
+    capture_events();
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let beneficiary = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &beneficiary.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_vested(
+        &owner,
+        &beneficiary.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+        1_000,
+        1_500,
+        2_000,
+    )
+    .await
+    .unwrap();
+
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(config.total_supply, sol(10.0));
+
+    // nothing is released before the cliff
+    warp_to_timestamp(&mut context, 1_400).await;
+    claim_vested(&beneficiary, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    assert!(context
+        .banks_client
+        .get_account(find_user_pda(&program_id, &mint, &beneficiary.pubkey()))
+        .await
+        .unwrap()
+        .is_none());
+
+    warp_to_timestamp(&mut context, 1_500).await;
+    claim_vested(&beneficiary, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    let beneficiary_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &beneficiary.pubkey(),
+    )
+    .await;
+    assert_eq!(beneficiary_account.balance, sol(5.0));
+
+    warp_to_timestamp(&mut context, 1_750).await;
+    claim_vested(&beneficiary, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    let beneficiary_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &beneficiary.pubkey(),
+    )
+    .await;
+    assert_eq!(beneficiary_account.balance, sol(7.5));
+
+    warp_to_timestamp(&mut context, 5_000).await;
+    claim_vested(&beneficiary, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    let beneficiary_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &beneficiary.pubkey(),
+    )
+    .await;
+    assert_eq!(beneficiary_account.balance, sol(10.0));
+
+    // the tokens show up as minted once they are claimed
+    let claims: Vec<Event> = emitted(&mint)
+        .into_iter()
+        .filter(|event| matches!(event, Event::Mint { .. }))
+        .collect();
+    assert_eq!(
+        claims,
+        [sol(5.0), sol(2.5), sol(2.5)]
+            .map(|amount| Event::Mint {
+                mint,
+                to: beneficiary.pubkey(),
+                amount,
+            })
+            .to_vec()
+    );
+
+    // the fully claimed schedule is closed
+    assert!(context
+        .banks_client
+        .get_account(find_vesting_pda(&program_id, &mint, &beneficiary.pubkey()))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_new_grant_after_full_claim() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let beneficiary = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &beneficiary.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_vested(
+        &owner,
+        &beneficiary.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+        1_000,
+        1_500,
+        2_000,
+    )
+    .await
+    .unwrap();
+
+    // a second grant can't be made while the first one is still vesting
+    let transaction_result = mint_vested(
+        &owner,
+        &beneficiary.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        3_000,
+        3_000,
+        4_000,
+    )
+    .await;
+    assert!(transaction_result.is_err());
+
+    warp_to_timestamp(&mut context, 2_000).await;
+    claim_vested(&beneficiary, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+
+    mint_vested(
+        &owner,
+        &beneficiary.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+        3_000,
+        3_000,
+        4_000,
+    )
+    .await
+    .unwrap();
+    let schedule = get_vesting_schedule(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &beneficiary.pubkey(),
+    )
+    .await;
+    assert_eq!(schedule.total_amount, sol(4.0));
+    assert_eq!(schedule.claimed, 0);
+
+    warp_to_timestamp(&mut context, 3_500).await;
+    claim_vested(&beneficiary, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    let beneficiary_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &beneficiary.pubkey(),
+    )
+    .await;
+    assert_eq!(beneficiary_account.balance, sol(12.0));
+}
+
+#[tokio::test]
+async fn test_fail_paused_claim_vested() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let beneficiary = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &beneficiary.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    mint_vested(
+        &owner,
+        &beneficiary.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+        1_000,
+        1_500,
+        2_000,
+    )
+    .await
+    .unwrap();
+    set_paused(&owner, &program_id, &mint, &mut context.banks_client, true)
+        .await
+        .unwrap();
+
+    warp_to_timestamp(&mut context, 2_000).await;
+    let transaction_result =
+        claim_vested(&beneficiary, &program_id, &mint, &mut context.banks_client).await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::Paused);
+}
+
+#[tokio::test]
+async fn test_fail_invalid_schedule_mint_vested() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let transaction_result = mint_vested(
+        &owner,
+        &Keypair::new().pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+        1_000,
+        2_500,
+        2_000,
+    )
+    .await;
+
+    assert_custom_error(
+        transaction_result,
+        SimpleTokenErrors::InvalidVestingSchedule,
+    );
+}
+
+#[tokio::test]
+async fn test_fail_not_minter_mint_vested() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let not_minter = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &not_minter.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = mint_vested(
+        &not_minter,
+        &not_minter.pubkey(),
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+        1_000,
+        1_500,
+        2_000,
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::InvalidOwner);
+}
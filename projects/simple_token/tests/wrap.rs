@@ -0,0 +1,259 @@
+use simple_token::errors::SimpleTokenErrors;
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+mod helpers;
+use crate::helpers::{
+    assert_custom_error, create_wrapped_token_account, get_account, get_config, get_token_account,
+    get_wrapped_mint, initialize, initialize_with_max_supply, initialize_wrapped_mint, mint_to,
+    prepare, set_paused, sol, transfer_sol, unwrap, wrap,
+};
+
+#[tokio::test]
+async fn test_wrap_unwrap() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    let holder = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &holder.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+    mint_to(
+        &owner,
+        &holder,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+
+    initialize_wrapped_mint(&owner, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    let token_account =
+        create_wrapped_token_account(&holder, &program_id, &mint, &mut context.banks_client).await;
+
+    wrap(
+        &holder,
+        &token_account,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+    )
+    .await
+    .unwrap();
+
+    let holder_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &holder.pubkey(),
+    )
+    .await;
+    let token_account_state = get_token_account(&mut context.banks_client, &token_account).await;
+    let wrapped_mint = get_wrapped_mint(&mut context.banks_client, &program_id, &mint).await;
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(holder_account.balance, sol(6.0));
+    assert_eq!(token_account_state.amount, sol(4.0));
+    assert_eq!(wrapped_mint.supply, sol(4.0));
+    assert_eq!(wrapped_mint.decimals, config.decimals);
+    assert_eq!(config.total_supply, sol(10.0));
+
+    unwrap(
+        &holder,
+        &token_account,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    let holder_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &holder.pubkey(),
+    )
+    .await;
+    let token_account_state = get_token_account(&mut context.banks_client, &token_account).await;
+    let wrapped_mint = get_wrapped_mint(&mut context.banks_client, &program_id, &mint).await;
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(holder_account.balance, sol(7.0));
+    assert_eq!(token_account_state.amount, sol(3.0));
+    assert_eq!(wrapped_mint.supply, sol(3.0));
+    assert_eq!(config.total_supply, sol(10.0));
+}
+
+#[tokio::test]
+async fn test_wrapped_tokens_count_towards_max_supply() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize_with_max_supply(
+        &owner,
+        &program_id,
+        &mut context.banks_client,
+        Some(sol(10.0)),
+    )
+    .await;
+
+    mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    initialize_wrapped_mint(&owner, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    let token_account =
+        create_wrapped_token_account(&owner, &program_id, &mint, &mut context.banks_client).await;
+    wrap(
+        &owner,
+        &token_account,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(5.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(5.0),
+    )
+    .await;
+    assert_custom_error(transaction_result, SimpleTokenErrors::MaxSupplyExceeded);
+
+    unwrap(
+        &owner,
+        &token_account,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(5.0),
+    )
+    .await
+    .unwrap();
+
+    let owner_account = get_account(
+        &mut context.banks_client,
+        &program_id,
+        &mint,
+        &owner.pubkey(),
+    )
+    .await;
+    let config = get_config(&mut context.banks_client, &program_id, &mint).await;
+    assert_eq!(owner_account.balance, sol(10.0));
+    assert_eq!(config.total_supply, sol(10.0));
+}
+
+#[tokio::test]
+async fn test_fail_unwrap_foreign_token_account() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    initialize_wrapped_mint(&owner, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    let token_account =
+        create_wrapped_token_account(&owner, &program_id, &mint, &mut context.banks_client).await;
+    wrap(
+        &owner,
+        &token_account,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+    )
+    .await
+    .unwrap();
+
+    let thief = Keypair::new();
+    transfer_sol(
+        &mut context.banks_client,
+        &context.payer,
+        &thief.pubkey(),
+        sol(1.0),
+    )
+    .await
+    .unwrap();
+
+    let transaction_result = unwrap(
+        &thief,
+        &token_account,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+    )
+    .await;
+
+    assert!(transaction_result.is_err());
+    let token_account_state = get_token_account(&mut context.banks_client, &token_account).await;
+    assert_eq!(token_account_state.amount, sol(4.0));
+}
+
+#[tokio::test]
+async fn test_fail_paused_wrap() {
+    let (mut context, program_id, owner) = prepare().await;
+    let mint = initialize(&owner, &program_id, &mut context.banks_client).await;
+
+    mint_to(
+        &owner,
+        &owner,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(10.0),
+    )
+    .await
+    .unwrap();
+    initialize_wrapped_mint(&owner, &program_id, &mint, &mut context.banks_client)
+        .await
+        .unwrap();
+    let token_account =
+        create_wrapped_token_account(&owner, &program_id, &mint, &mut context.banks_client).await;
+    set_paused(&owner, &program_id, &mint, &mut context.banks_client, true)
+        .await
+        .unwrap();
+
+    let transaction_result = wrap(
+        &owner,
+        &token_account,
+        &program_id,
+        &mint,
+        &mut context.banks_client,
+        sol(4.0),
+    )
+    .await;
+
+    assert_custom_error(transaction_result, SimpleTokenErrors::Paused);
+}